@@ -0,0 +1,100 @@
+//! Compares every Fibonacci algorithm across n and integer types.
+//!
+//! ```bash
+//! cargo bench --bench algorithms                           # everything
+//! cargo bench --bench algorithms -- sequence               # one group
+//! cargo bench --bench algorithms -- --save-baseline main   # record a baseline
+//! cargo bench --bench algorithms -- --baseline main        # compare against it
+//! ```
+//!
+//! After a run the estimates are collected into `target/criterion/summary.json`
+//! and printed as a Markdown table.
+
+mod summary;
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion};
+use fibonacci_sequence::fibonacci;
+
+/// Indices small enough for the exponential algorithms
+const SMALL_N: [u32; 6] = [5, 10, 15, 20, 25, 30];
+
+/// Recursive F(n) against the memoized and O(log n) versions, as u64
+fn nth_u64(c: &mut Criterion) {
+    let mut group = c.benchmark_group("nth_u64");
+    for n in SMALL_N {
+        group.bench_with_input(BenchmarkId::new("fib", n), &n, |b, &n| b.iter(|| fibonacci::fib(black_box(n))));
+        group.bench_with_input(BenchmarkId::new("fib_memoized", n), &n, |b, &n| {
+            b.iter(|| fibonacci::fib_memoized(black_box(n)))
+        });
+        group.bench_with_input(BenchmarkId::new("fib_checked", n), &n, |b, &n| {
+            b.iter(|| fibonacci::fib_checked(black_box(n)))
+        });
+    }
+    group.finish();
+}
+
+/// The polynomial algorithms up to the largest index of each integer type
+fn nth_wide(c: &mut Criterion) {
+    let mut group = c.benchmark_group("nth_wide");
+    for n in [50, 93] {
+        group.bench_with_input(BenchmarkId::new("fib_memoized", n), &n, |b, &n| {
+            b.iter(|| fibonacci::fib_memoized(black_box(n)))
+        });
+        group.bench_with_input(BenchmarkId::new("fib_checked", n), &n, |b, &n| {
+            b.iter(|| fibonacci::fib_checked(black_box(n)))
+        });
+    }
+    for n in [93, 186] {
+        group.bench_with_input(BenchmarkId::new("fib_u128", n), &n, |b, &n| {
+            b.iter(|| fibonacci::fib_u128(black_box(n)))
+        });
+    }
+    for n in [93u64, 186, 1_000, 10_000, 100_000] {
+        group.bench_with_input(BenchmarkId::new("fib_big", n), &n, |b, &n| b.iter(|| fibonacci::fib_big(black_box(n))));
+    }
+    group.finish();
+}
+
+/// F(n) mod m, which never leaves u64 however large n is
+fn nth_mod(c: &mut Criterion) {
+    let mut group = c.benchmark_group("nth_mod");
+    for n in [1_000u64, 1_000_000_000, u64::MAX] {
+        group.bench_with_input(BenchmarkId::new("fib_mod", n), &n, |b, &n| {
+            b.iter(|| fibonacci::fib_mod(black_box(n), black_box(1_000_000_007)))
+        });
+    }
+    group.finish();
+}
+
+/// F(0) through F(n): one recursive call per term against a single pass
+fn sequence(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sequence");
+    for n in [5, 10, 15, 20, 25] {
+        group.bench_with_input(BenchmarkId::new("generate_sequence", n), &n, |b, &n| {
+            b.iter(|| fibonacci::generate_sequence(black_box(n)))
+        });
+        group.bench_with_input(BenchmarkId::new("generate_sequence_iterative", n), &n, |b, &n| {
+            b.iter(|| fibonacci::generate_sequence_iterative(black_box(n)))
+        });
+    }
+    group.bench_with_input(BenchmarkId::new("generate_sequence_iterative", 93), &93, |b, &n| {
+        b.iter(|| fibonacci::generate_sequence_iterative(black_box(n)))
+    });
+    group.finish();
+}
+
+fn main() {
+    let mut criterion = Criterion::default().configure_from_args();
+    nth_u64(&mut criterion);
+    nth_wide(&mut criterion);
+    nth_mod(&mut criterion);
+    sequence(&mut criterion);
+    criterion.final_summary();
+
+    // `cargo test --benches` runs each benchmark once and records nothing
+    if std::env::args().any(|arg| arg == "--bench") {
+        summary::report();
+    }
+}
@@ -0,0 +1,115 @@
+//! Collects criterion's per-benchmark estimates into one JSON file and a table.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Value, json};
+
+/// One measured benchmark
+struct Row {
+    group: String,
+    function: String,
+    n: u64,
+    mean_ns: f64,
+    median_ns: f64,
+    std_err_ns: f64,
+}
+
+/// Where criterion keeps its results: `$CRITERION_HOME`, else `target/criterion`
+fn criterion_dir() -> Option<PathBuf> {
+    if let Some(home) = std::env::var_os("CRITERION_HOME") {
+        return Some(home.into());
+    }
+    // The bench binary lives in target/<profile>/deps/
+    let exe = std::env::current_exe().ok()?;
+    Some(exe.ancestors().nth(3)?.join("criterion"))
+}
+
+/// Every `new/` result directory below `dir`
+fn result_dirs(dir: &Path, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        if path.file_name().is_some_and(|name| name == "new") {
+            found.push(path);
+        } else {
+            result_dirs(&path, found);
+        }
+    }
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn read_row(dir: &Path) -> Option<Row> {
+    let benchmark = read_json(&dir.join("benchmark.json"))?;
+    let estimates = read_json(&dir.join("estimates.json"))?;
+    Some(Row {
+        group: benchmark["group_id"].as_str()?.to_string(),
+        function: benchmark["function_id"].as_str()?.to_string(),
+        n: benchmark["value_str"].as_str()?.parse().ok()?,
+        mean_ns: estimates["mean"]["point_estimate"].as_f64()?,
+        median_ns: estimates["median"]["point_estimate"].as_f64()?,
+        std_err_ns: estimates["mean"]["standard_error"].as_f64()?,
+    })
+}
+
+/// Human-readable duration with three significant digits
+fn format_ns(ns: f64) -> String {
+    match ns {
+        ns if ns < 1e3 => format!("{:.3} ns", ns),
+        ns if ns < 1e6 => format!("{:.3} µs", ns / 1e3),
+        ns if ns < 1e9 => format!("{:.3} ms", ns / 1e6),
+        ns => format!("{:.3} s", ns / 1e9),
+    }
+}
+
+/// Write `summary.json` next to criterion's results and print a Markdown table
+pub fn report() {
+    let Some(dir) = criterion_dir() else { return };
+    let mut dirs = Vec::new();
+    result_dirs(&dir, &mut dirs);
+    let mut rows: Vec<Row> = dirs.iter().filter_map(|dir| read_row(dir)).collect();
+    if rows.is_empty() {
+        return;
+    }
+    rows.sort_by(|a, b| (&a.group, a.n, &a.function).cmp(&(&b.group, b.n, &b.function)));
+
+    let json: Vec<Value> = rows
+        .iter()
+        .map(|row| {
+            json!({
+                "group": row.group,
+                "function": row.function,
+                "n": row.n,
+                "mean_ns": row.mean_ns,
+                "median_ns": row.median_ns,
+                "std_err_ns": row.std_err_ns,
+            })
+        })
+        .collect();
+    let path = dir.join("summary.json");
+    let written = serde_json::to_string_pretty(&json).is_ok_and(|text| fs::write(&path, text).is_ok());
+    if written {
+        println!("\nWrote {}\n", path.display());
+    } else {
+        eprintln!("Could not write {}", path.display());
+    }
+
+    println!("| group | function | n | mean | ± std. err. |");
+    println!("|---|---|---:|---:|---:|");
+    for row in &rows {
+        println!(
+            "| {} | {} | {} | {} | {} |",
+            row.group,
+            row.function,
+            row.n,
+            format_ns(row.mean_ns),
+            format_ns(row.std_err_ns)
+        );
+    }
+}
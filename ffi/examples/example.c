@@ -0,0 +1,47 @@
+/*
+ * Build the library, then compile against the static archive:
+ *
+ *   cargo build -p fibonacci_ffi --release
+ *   cc ffi/examples/example.c -Iffi/include target/release/libfibonacci.a -lpthread -ldl -lm -o example
+ */
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "fibonacci.h"
+
+int main(void) {
+    uint64_t value;
+    FibStatus status = fib_nth(90, &value);
+    if (status != FIB_STATUS_OK) {
+        fprintf(stderr, "fib_nth: %s\n", fib_status_message(status));
+        return 1;
+    }
+    printf("F(90) = %llu\n", (unsigned long long)value);
+
+    status = fib_nth(94, &value);
+    printf("F(94) as u64: %s\n", fib_status_message(status));
+
+    uint64_t terms[10];
+    fib_fill_sequence(0, terms, 10);
+    printf("F(0..10) =");
+    for (size_t i = 0; i < 10; i++) {
+        printf(" %llu", (unsigned long long)terms[i]);
+    }
+    printf("\n");
+
+    /* Ask for the size first, then fill an exactly sized buffer */
+    size_t required;
+    fib_big_to_string(1000, NULL, 0, &required);
+    char *digits = malloc(required);
+    if (digits == NULL) {
+        return 1;
+    }
+    status = fib_big_to_string(1000, digits, required, &required);
+    if (status == FIB_STATUS_OK) {
+        printf("F(1000) has %zu digits: %.20s...\n", required - 1, digits);
+    }
+    free(digits);
+
+    printf("144 is %sa Fibonacci number\n", fib_is_fibonacci_number(144) ? "" : "not ");
+    return 0;
+}
@@ -0,0 +1,82 @@
+#ifndef FIBONACCI_H
+#define FIBONACCI_H
+
+/* Generated by cbindgen from ffi/src/lib.rs; do not edit. Regenerate with FIBONACCI_BLESS=1 cargo test -p fibonacci_ffi */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// Result codes returned by every fallible function
+typedef enum {
+  // Success; the out-pointer holds the result
+  FIB_STATUS_OK = 0,
+  // A required pointer argument was NULL
+  FIB_STATUS_NULL_POINTER = 1,
+  // The result does not fit in the requested integer type
+  FIB_STATUS_OVERFLOW = 2,
+  // The modulus was zero
+  FIB_STATUS_ZERO_MODULUS = 3,
+  // The caller's buffer is too small; the required size was reported
+  FIB_STATUS_BUFFER_TOO_SMALL = 4,
+  // n is above the supported limit for arbitrary-precision results
+  FIB_STATUS_OUT_OF_RANGE = 5,
+} FibStatus;
+
+// F(n) as a u64
+//
+// # Returns
+// `Overflow` for n > 93, leaving `*out` untouched
+//
+// # Safety
+// `out` must be NULL or valid for writing a `uint64_t`.
+FibStatus fib_nth(uint32_t n, uint64_t *out);
+
+// F(n) as a 128-bit value split into high and low 64-bit halves
+//
+// # Returns
+// `Overflow` for n > 186
+//
+// # Safety
+// `high` and `low` must be NULL or valid for writing a `uint64_t`.
+FibStatus fib_nth_u128(uint32_t n, uint64_t *high, uint64_t *low);
+
+// F(n) mod m for any n, in O(log n)
+//
+// # Safety
+// `out` must be NULL or valid for writing a `uint64_t`.
+FibStatus fib_mod(uint64_t n, uint64_t m, uint64_t *out);
+
+// Whether `value` is a Fibonacci number; exact for every u64
+bool fib_is_fibonacci_number(uint64_t value);
+
+// Fill `buffer[0..len]` with F(start), F(start + 1), ...
+//
+// # Returns
+// `Overflow` if F(start + len - 1) does not fit in a u64, in which case the
+// buffer is left untouched. A zero `len` succeeds without touching `buffer`.
+//
+// # Safety
+// `buffer` must be NULL or valid for writing `len` `uint64_t`s.
+FibStatus fib_fill_sequence(uint32_t start, uint64_t *buffer, size_t len);
+
+// Write F(n) in decimal as a NUL-terminated string
+//
+// Call with a NULL `buffer` (or a small `capacity`) to learn the size:
+// `*required` always receives the length including the terminating NUL.
+//
+// # Returns
+// `BufferTooSmall` if `capacity < *required`, or `OutOfRange` for n > 100000
+//
+// # Safety
+// `required` must be valid for writing a `size_t`, and `buffer` must be NULL
+// or valid for writing `capacity` bytes.
+FibStatus fib_big_to_string(uint64_t n, char *buffer, size_t capacity, size_t *required);
+
+// A static, human-readable description of a status code
+//
+// Takes the code as a plain `int`: C lets any integer through a `FibStatus`
+// parameter, and an unknown value must not reach a Rust enum.
+const char *fib_status_message(int status);
+
+#endif  /* FIBONACCI_H */
@@ -0,0 +1,318 @@
+//! C ABI for the `fibonacci_sequence` math core.
+//!
+//! Built as `libfibonacci.so` / `libfibonacci.a` with the header
+//! `include/fibonacci.h`. Every fallible function returns a [`FibStatus`] and
+//! writes its result through an out-pointer; nothing here panics, because the
+//! release profile turns a panic into an abort of the host process.
+
+use std::ffi::{c_char, c_int, CStr};
+use std::ptr;
+
+use fibonacci_sequence::fibonacci;
+use fibonacci_sequence::validation::{MAX_BIG_N, MAX_U64_N};
+
+/// Result codes returned by every fallible function
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FibStatus {
+    /// Success; the out-pointer holds the result
+    Ok = 0,
+    /// A required pointer argument was NULL
+    NullPointer = 1,
+    /// The result does not fit in the requested integer type
+    Overflow = 2,
+    /// The modulus was zero
+    ZeroModulus = 3,
+    /// The caller's buffer is too small; the required size was reported
+    BufferTooSmall = 4,
+    /// n is above the supported limit for arbitrary-precision results
+    OutOfRange = 5,
+}
+
+/// F(n) as a u64
+///
+/// # Returns
+/// `Overflow` for n > 93, leaving `*out` untouched
+///
+/// # Safety
+/// `out` must be NULL or valid for writing a `uint64_t`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fib_nth(n: u32, out: *mut u64) -> FibStatus {
+    if out.is_null() {
+        return FibStatus::NullPointer;
+    }
+    match fibonacci::fib_checked(n) {
+        Some(value) => {
+            // SAFETY: checked for NULL above, validity is the caller's contract
+            unsafe { out.write(value) };
+            FibStatus::Ok
+        }
+        None => FibStatus::Overflow,
+    }
+}
+
+/// F(n) as a 128-bit value split into high and low 64-bit halves
+///
+/// # Returns
+/// `Overflow` for n > 186
+///
+/// # Safety
+/// `high` and `low` must be NULL or valid for writing a `uint64_t`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fib_nth_u128(n: u32, high: *mut u64, low: *mut u64) -> FibStatus {
+    if high.is_null() || low.is_null() {
+        return FibStatus::NullPointer;
+    }
+    match fibonacci::fib_u128(n) {
+        Some(value) => {
+            // SAFETY: checked for NULL above, validity is the caller's contract
+            unsafe {
+                high.write((value >> 64) as u64);
+                low.write(value as u64);
+            }
+            FibStatus::Ok
+        }
+        None => FibStatus::Overflow,
+    }
+}
+
+/// F(n) mod m for any n, in O(log n)
+///
+/// # Safety
+/// `out` must be NULL or valid for writing a `uint64_t`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fib_mod(n: u64, m: u64, out: *mut u64) -> FibStatus {
+    if out.is_null() {
+        return FibStatus::NullPointer;
+    }
+    match fibonacci::fib_mod(n, m) {
+        Some(value) => {
+            // SAFETY: checked for NULL above, validity is the caller's contract
+            unsafe { out.write(value) };
+            FibStatus::Ok
+        }
+        None => FibStatus::ZeroModulus,
+    }
+}
+
+/// Whether `value` is a Fibonacci number; exact for every u64
+#[unsafe(no_mangle)]
+pub extern "C" fn fib_is_fibonacci_number(value: u64) -> bool {
+    fibonacci::is_fibonacci_number(value)
+}
+
+/// Fill `buffer[0..len]` with F(start), F(start + 1), ...
+///
+/// # Returns
+/// `Overflow` if F(start + len - 1) does not fit in a u64, in which case the
+/// buffer is left untouched. A zero `len` succeeds without touching `buffer`.
+///
+/// # Safety
+/// `buffer` must be NULL or valid for writing `len` `uint64_t`s.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fib_fill_sequence(start: u32, buffer: *mut u64, len: usize) -> FibStatus {
+    if len == 0 {
+        return FibStatus::Ok;
+    }
+    if buffer.is_null() {
+        return FibStatus::NullPointer;
+    }
+    let last = u32::try_from(len - 1).ok().and_then(|offset| start.checked_add(offset));
+    if last.is_none_or(|last| last > MAX_U64_N) {
+        return FibStatus::Overflow;
+    }
+
+    // Every written term fits; only the unused successor of F(93) may not
+    let mut a = fibonacci::fib_checked(start).unwrap_or_default();
+    let mut b = fibonacci::fib_checked(start + 1).unwrap_or_default();
+    for i in 0..len {
+        // SAFETY: i < len, and the buffer holds len values by contract
+        unsafe { buffer.add(i).write(a) };
+        (a, b) = (b, a.wrapping_add(b));
+    }
+    FibStatus::Ok
+}
+
+/// Write F(n) in decimal as a NUL-terminated string
+///
+/// Call with a NULL `buffer` (or a small `capacity`) to learn the size:
+/// `*required` always receives the length including the terminating NUL.
+///
+/// # Returns
+/// `BufferTooSmall` if `capacity < *required`, or `OutOfRange` for n > 100000
+///
+/// # Safety
+/// `required` must be valid for writing a `size_t`, and `buffer` must be NULL
+/// or valid for writing `capacity` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fib_big_to_string(
+    n: u64,
+    buffer: *mut c_char,
+    capacity: usize,
+    required: *mut usize,
+) -> FibStatus {
+    if required.is_null() {
+        return FibStatus::NullPointer;
+    }
+    if n > MAX_BIG_N as u64 {
+        return FibStatus::OutOfRange;
+    }
+
+    let digits = fibonacci::fib_big(n).to_string();
+    let needed = digits.len() + 1;
+    // SAFETY: checked for NULL above, validity is the caller's contract
+    unsafe { required.write(needed) };
+
+    if buffer.is_null() || capacity < needed {
+        return FibStatus::BufferTooSmall;
+    }
+    // SAFETY: the buffer holds at least `needed` bytes, and a String never
+    // overlaps caller memory
+    unsafe {
+        ptr::copy_nonoverlapping(digits.as_ptr(), buffer.cast::<u8>(), digits.len());
+        buffer.add(digits.len()).write(0);
+    }
+    FibStatus::Ok
+}
+
+impl FibStatus {
+    /// Every status, in code order
+    const ALL: [FibStatus; 6] = [
+        FibStatus::Ok,
+        FibStatus::NullPointer,
+        FibStatus::Overflow,
+        FibStatus::ZeroModulus,
+        FibStatus::BufferTooSmall,
+        FibStatus::OutOfRange,
+    ];
+
+    /// The status with this code, if there is one
+    fn from_code(code: c_int) -> Option<Self> {
+        Self::ALL.into_iter().find(|&status| status as c_int == code)
+    }
+}
+
+/// A static, human-readable description of a status code
+///
+/// Takes the code as a plain `int`: C lets any integer through a `FibStatus`
+/// parameter, and an unknown value must not reach a Rust enum.
+#[unsafe(no_mangle)]
+pub extern "C" fn fib_status_message(status: c_int) -> *const c_char {
+    let message: &'static CStr = match FibStatus::from_code(status) {
+        Some(FibStatus::Ok) => c"success",
+        Some(FibStatus::NullPointer) => c"a required pointer argument was NULL",
+        Some(FibStatus::Overflow) => c"the result does not fit in the integer type",
+        Some(FibStatus::ZeroModulus) => c"the modulus must be at least 1",
+        Some(FibStatus::BufferTooSmall) => c"the buffer is too small",
+        Some(FibStatus::OutOfRange) => c"n is above the supported limit",
+        None => c"unknown status",
+    };
+    message.as_ptr()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nth() {
+        let mut out = 0;
+        assert_eq!(unsafe { fib_nth(93, &mut out) }, FibStatus::Ok);
+        assert_eq!(out, 12200160415121876738);
+        assert_eq!(unsafe { fib_nth(94, &mut out) }, FibStatus::Overflow);
+        assert_eq!(out, 12200160415121876738);
+        assert_eq!(unsafe { fib_nth(10, ptr::null_mut()) }, FibStatus::NullPointer);
+
+        let (mut high, mut low) = (0, 0);
+        assert_eq!(unsafe { fib_nth_u128(186, &mut high, &mut low) }, FibStatus::Ok);
+        assert_eq!(((high as u128) << 64) | low as u128, fibonacci::fib_u128(186).unwrap());
+        assert_eq!(unsafe { fib_nth_u128(187, &mut high, &mut low) }, FibStatus::Overflow);
+    }
+
+    #[test]
+    fn test_mod_and_membership() {
+        let mut out = 0;
+        assert_eq!(unsafe { fib_mod(1_000_000_000_000, 10, &mut out) }, FibStatus::Ok);
+        assert_eq!(out, 5);
+        assert_eq!(unsafe { fib_mod(5, 0, &mut out) }, FibStatus::ZeroModulus);
+        assert!(fib_is_fibonacci_number(12200160415121876738));
+        assert!(!fib_is_fibonacci_number(u64::MAX));
+    }
+
+    #[test]
+    fn test_fill_sequence() {
+        let mut buffer = [0u64; 6];
+        assert_eq!(unsafe { fib_fill_sequence(5, buffer.as_mut_ptr(), buffer.len()) }, FibStatus::Ok);
+        assert_eq!(buffer, [5, 8, 13, 21, 34, 55]);
+
+        let mut tail = [0u64; 2];
+        assert_eq!(unsafe { fib_fill_sequence(92, tail.as_mut_ptr(), 2) }, FibStatus::Ok);
+        assert_eq!(tail, [7540113804746346429, 12200160415121876738]);
+
+        let mut untouched = [7u64; 3];
+        assert_eq!(unsafe { fib_fill_sequence(92, untouched.as_mut_ptr(), 3) }, FibStatus::Overflow);
+        assert_eq!(untouched, [7; 3]);
+        assert_eq!(unsafe { fib_fill_sequence(u32::MAX, untouched.as_mut_ptr(), 3) }, FibStatus::Overflow);
+        assert_eq!(unsafe { fib_fill_sequence(0, untouched.as_mut_ptr(), usize::MAX) }, FibStatus::Overflow);
+        assert_eq!(unsafe { fib_fill_sequence(0, ptr::null_mut(), 0) }, FibStatus::Ok);
+        assert_eq!(unsafe { fib_fill_sequence(0, ptr::null_mut(), 1) }, FibStatus::NullPointer);
+    }
+
+    #[test]
+    fn test_big_to_string() {
+        let mut required = 0;
+        assert_eq!(
+            unsafe { fib_big_to_string(100, ptr::null_mut(), 0, &mut required) },
+            FibStatus::BufferTooSmall
+        );
+        assert_eq!(required, 22);
+
+        let mut buffer = vec![0 as c_char; required];
+        assert_eq!(
+            unsafe { fib_big_to_string(100, buffer.as_mut_ptr(), buffer.len(), &mut required) },
+            FibStatus::Ok
+        );
+        let text = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+        assert_eq!(text.to_str(), Ok("354224848179261915075"));
+
+        assert_eq!(
+            unsafe { fib_big_to_string(100, buffer.as_mut_ptr(), 21, &mut required) },
+            FibStatus::BufferTooSmall
+        );
+        assert_eq!(
+            unsafe { fib_big_to_string(u64::MAX, buffer.as_mut_ptr(), buffer.len(), &mut required) },
+            FibStatus::OutOfRange
+        );
+        assert_eq!(
+            unsafe { fib_big_to_string(1, buffer.as_mut_ptr(), buffer.len(), ptr::null_mut()) },
+            FibStatus::NullPointer
+        );
+    }
+
+    #[test]
+    fn test_status_messages() {
+        let message = |status| unsafe { CStr::from_ptr(fib_status_message(status)) }.to_str();
+        assert_eq!(message(FibStatus::ZeroModulus as c_int), Ok("the modulus must be at least 1"));
+        assert_eq!(message(FibStatus::OutOfRange as c_int), Ok("n is above the supported limit"));
+        assert_eq!(message(6), Ok("unknown status"));
+        assert_eq!(message(-1), Ok("unknown status"));
+    }
+
+    /// Regenerate with `FIBONACCI_BLESS=1 cargo test -p fibonacci_ffi`
+    #[test]
+    fn test_header_is_up_to_date() {
+        let crate_dir = env!("CARGO_MANIFEST_DIR");
+        let mut generated = Vec::new();
+        cbindgen::generate(crate_dir)
+            .expect("cbindgen failed")
+            .write(&mut generated);
+        let generated = String::from_utf8(generated).unwrap();
+
+        let path = std::path::Path::new(crate_dir).join("include/fibonacci.h");
+        if std::env::var_os("FIBONACCI_BLESS").is_some() {
+            std::fs::write(&path, &generated).unwrap();
+        }
+        let committed = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(committed == generated, "include/fibonacci.h is stale; rerun with FIBONACCI_BLESS=1");
+    }
+}
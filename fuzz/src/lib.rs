@@ -0,0 +1,151 @@
+//! Checks shared by the fuzz targets and the corpus replay test.
+//!
+//! Each function takes raw fuzzer bytes, feeds them to the library and panics
+//! if an invariant breaks. Returning an error is always fine; a panic, an
+//! overflow or a round trip that changes the value is a bug.
+
+use std::borrow::Cow;
+
+use clap::Parser;
+use fibonacci_sequence::cli::{self, Cli};
+use fibonacci_sequence::export::{self, ExportFormat};
+use fibonacci_sequence::validation::{self, MAX_BIG_DIGITS, MAX_BIG_N, MAX_FIBONACCI_N, MAX_U64_N};
+use fibonacci_sequence::{fibonacci, phinary, radix, server, zeckendorf};
+
+/// Inputs longer than this are skipped by the superlinear parsers
+const MAX_TEXT_LEN: usize = 4_096;
+
+fn text(data: &[u8]) -> Cow<'_, str> {
+    String::from_utf8_lossy(data)
+}
+
+/// `validation::validate_input` and the helpers built on it
+pub fn validate_input(data: &[u8]) {
+    let input = text(data);
+    let result = validation::validate_input(&input);
+
+    assert_eq!(validation::is_valid_input(&input), result.is_ok());
+    assert_eq!(validation::get_error_message(&input), result.clone().err().map(|e| e.to_string()));
+    if let Ok(n) = result {
+        assert!(n <= MAX_FIBONACCI_N);
+        assert_eq!(input.trim().parse::<u32>(), Ok(n));
+    }
+
+    for max in [0, MAX_FIBONACCI_N, MAX_U64_N, MAX_BIG_N, u32::MAX] {
+        if let Ok(n) = validation::validate_input_with_max(&input, max) {
+            assert!(n <= max);
+        }
+    }
+}
+
+/// Number parsers: plain and arbitrary-precision values, moduli, ranges,
+/// any radix and base φ. The first byte picks the radix.
+pub fn numbers(data: &[u8]) {
+    let Some((&selector, rest)) = data.split_first() else { return };
+    let input = text(rest);
+    if input.len() > MAX_TEXT_LEN {
+        return;
+    }
+
+    let _ = validation::validate_value(&input);
+    if let Ok(m) = validation::validate_modulus(&input) {
+        assert!(m >= 1);
+    }
+    if let Ok(value) = validation::validate_big_value(&input) {
+        assert!(value.to_string().len() <= MAX_BIG_DIGITS);
+    }
+    if let Some((from, to)) = input.split_once(',')
+        && let Ok((from, to)) = validation::validate_range(from, to, MAX_BIG_N)
+    {
+        assert!(from <= to && to <= MAX_BIG_N);
+    }
+
+    // Out-of-range radixes must be rejected, not panic
+    let base = u32::from(selector % 40);
+    if let Ok(value) = radix::from_radix(&input, base) {
+        let digits = radix::to_radix(&value, base).expect("a radix that parsed also formats");
+        assert_eq!(radix::from_radix(&digits, base), Ok(value));
+    }
+
+    if let Ok(value) = phinary::parse_phinary(&input) {
+        // The standard form is a fixed point and denotes the same number
+        let standard = value.to_string();
+        assert_eq!(phinary::parse_phinary(&standard).as_ref(), Ok(&value));
+        assert_eq!(phinary::normalize(&standard), Ok(standard));
+    }
+}
+
+/// Zeckendorf digits and the Fibonacci universal code
+pub fn fibonacci_code(data: &[u8]) {
+    // Any u64 decomposes into non-consecutive Fibonacci numbers and back
+    if let Some(bytes) = data.get(..8) {
+        let n = u64::from_le_bytes(bytes.try_into().expect("8 bytes"));
+        let indices = zeckendorf::zeckendorf(n);
+        assert!(zeckendorf::is_zeckendorf(&indices));
+        let total: u64 = indices.iter().map(|&i| fibonacci::fib_checked(i).expect("index fits")).sum();
+        assert_eq!(total, n);
+        let digits = zeckendorf::to_fibonacci_base(n);
+        assert_eq!(zeckendorf::from_fibonacci_base(&digits), Ok(n));
+    }
+
+    let input = text(data);
+    if let Ok(n) = zeckendorf::from_fibonacci_base(&input) {
+        let canonical = zeckendorf::to_fibonacci_base(n);
+        assert_eq!(zeckendorf::from_fibonacci_base(&canonical), Ok(n));
+        // A digit string without adjacent 1s or leading zeros is already canonical
+        let digits = input.trim();
+        if !digits.contains("11") && (digits == "0" || !digits.starts_with('0')) {
+            assert_eq!(canonical, digits);
+        }
+    }
+
+    if let Ok(values) = zeckendorf::fibonacci_decode(&input) {
+        let encoded: String = values
+            .iter()
+            .map(|&v| zeckendorf::fibonacci_encode(v).expect("decoded values are at least 1"))
+            .collect();
+        assert_eq!(encoded, input);
+    }
+}
+
+/// CSV, JSON and NDJSON import. The first byte picks the format.
+pub fn import(data: &[u8]) {
+    let Some((&selector, rest)) = data.split_first() else { return };
+    let format = ExportFormat::ALL[selector as usize % ExportFormat::ALL.len()];
+    let input = text(rest);
+
+    if let Ok(sequence) = export::import(&input, format) {
+        let n = u32::try_from(sequence.len() - 1).expect("imports are at most 94 terms");
+        assert_eq!(sequence, fibonacci::generate_sequence_iterative(n));
+        let exported = export::export(&sequence, format).expect("a valid sequence exports");
+        assert_eq!(export::import(&exported, format), Ok(sequence));
+    }
+}
+
+/// HTTP routes and command lines: the first line is a URL, the rest are
+/// whitespace-separated `fib` arguments
+pub fn requests(data: &[u8]) {
+    let input = text(data);
+    if input.len() > MAX_TEXT_LEN {
+        return;
+    }
+    let (url, args) = input.split_once('\n').unwrap_or((&input, ""));
+
+    let response = server::handle("GET", url);
+    assert!(matches!(response.status, 200 | 400 | 404), "{} for {}", response.status, url);
+    assert_eq!(response.status != 200, response.body.get("error").is_some());
+
+    // Keep `cache prune` and cached results away from the real cache directory,
+    // and run with the default settings whatever the user's settings file says
+    let cache_dir = std::env::temp_dir().join("fibonacci_fuzz_cache");
+    let config = std::env::temp_dir().join("fibonacci_fuzz_config.toml");
+    let args = std::iter::once("fib").chain(args.split_whitespace()).chain([
+        "--cache-dir",
+        cache_dir.to_str().expect("temp dir is UTF-8"),
+        "--config",
+        config.to_str().expect("temp dir is UTF-8"),
+    ]);
+    if let Ok(cli) = Cli::try_parse_from(args) {
+        let _ = cli::run(&cli);
+    }
+}
@@ -0,0 +1,43 @@
+//! Replays the seed corpus and every saved regression case on stable Rust.
+
+use std::fs;
+use std::path::Path;
+
+fn replay(target: &str, check: fn(&[u8])) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut count = 0;
+    for dir in ["corpus", "regressions"] {
+        let Ok(entries) = fs::read_dir(root.join(dir).join(target)) else { continue };
+        for entry in entries {
+            let path = entry.unwrap().path();
+            check(&fs::read(&path).unwrap());
+            count += 1;
+        }
+    }
+    assert!(count > 0, "no inputs for {}", target);
+}
+
+#[test]
+fn validate_input() {
+    replay("validate_input", fibonacci_fuzz::validate_input);
+}
+
+#[test]
+fn numbers() {
+    replay("numbers", fibonacci_fuzz::numbers);
+}
+
+#[test]
+fn fibonacci_code() {
+    replay("fibonacci_code", fibonacci_fuzz::fibonacci_code);
+}
+
+#[test]
+fn import() {
+    replay("import", fibonacci_fuzz::import);
+}
+
+#[test]
+fn requests() {
+    replay("requests", fibonacci_fuzz::requests);
+}
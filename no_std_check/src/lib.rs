@@ -0,0 +1,81 @@
+//! Compile-time check that the `fibonacci_sequence` math core is `no_std`.
+//!
+//! This crate links nothing but `core` (and `alloc` with the default `alloc`
+//! feature). Build it on its own so that features are not unified with the
+//! GUI frontends, for a target without `std`; the `no-std` CI job runs:
+//!
+//! ```text
+//! rustup target add thumbv7em-none-eabihf
+//! cargo build -p fibonacci_no_std_check --target thumbv7em-none-eabihf --no-default-features
+//! cargo build -p fibonacci_no_std_check --target thumbv7em-none-eabihf
+//! ```
+
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use fibonacci_sequence::{fibonacci, sums, zeckendorf};
+
+/// F(n) mod m by fast doubling
+pub fn fib_mod(n: u64, m: u64) -> Option<u64> {
+    fibonacci::fib_mod(n, m)
+}
+
+/// F(n), or `None` if it does not fit in a u64
+pub fn fib_checked(n: u32) -> Option<u64> {
+    fibonacci::fib_checked(n)
+}
+
+/// Σ F(i) for i in `from..=to` modulo `m`
+pub fn sum_range_mod(from: u64, to: u64, m: u64) -> Option<u64> {
+    sums::sum_range_mod(from, to, m)
+}
+
+/// Write the Zeckendorf indices of n into `out`, returning how many were written
+///
+/// A u64 never needs more than 46 terms.
+pub fn zeckendorf_into(n: u64, out: &mut [u32; 46]) -> usize {
+    let mut len = 0;
+    for (slot, index) in out.iter_mut().zip(zeckendorf::zeckendorf_terms(n)) {
+        *slot = index;
+        len += 1;
+    }
+    len
+}
+
+/// F(0) through F(n) in a heap-allocated vector
+#[cfg(feature = "alloc")]
+pub fn sequence(n: u32) -> alloc::vec::Vec<u64> {
+    fibonacci::generate_sequence_iterative(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_core_routines() {
+        assert_eq!(fib_checked(93), Some(12200160415121876738));
+        assert_eq!(fib_mod(1_000_000_000_000, 10), fib_mod(1_000_000_000_000 % 60, 10));
+        assert_eq!(sum_range_mod(0, 10, 1000), Some(143));
+        assert!(fibonacci::is_fibonacci_number(6765));
+
+        let mut out = [0; 46];
+        let len = zeckendorf_into(100, &mut out);
+        assert_eq!(&out[..len], &[11, 6, 4]);
+        assert!(zeckendorf::is_zeckendorf(&out[..len]));
+
+        // The longest decomposition of a u64 fits the buffer
+        let len = zeckendorf_into(u64::MAX, &mut out);
+        assert!(len <= out.len());
+        assert_eq!(out[0], 93);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_alloc_routines() {
+        assert_eq!(sequence(10)[10], 55);
+        assert_eq!(zeckendorf::zeckendorf(100), alloc::vec![11, 6, 4]);
+    }
+}
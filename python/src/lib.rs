@@ -0,0 +1,214 @@
+//! Python extension module for the `fibonacci_sequence` math core.
+//!
+//! Build it into the active virtualenv with `maturin develop` from this
+//! directory, then `import fibonacci_sequence`. Fibonacci values come back as
+//! Python ints of any size.
+//!
+//! Arguments go through [`fibonacci_sequence::validation`], so limits and
+//! messages match the other frontends. An [`InputError`] raises
+//! `FibonacciError`, a `ValueError` subclass, except that a zero modulus raises
+//! `ZeroDivisionError`.
+
+use num_bigint::{BigInt, BigUint};
+use pyo3::create_exception;
+use pyo3::exceptions::{PyValueError, PyZeroDivisionError};
+use pyo3::prelude::*;
+
+use fibonacci_sequence::error::InputError;
+use fibonacci_sequence::validation::{self, MAX_BIG_N};
+use fibonacci_sequence::fibonacci;
+use fibonacci_sequence::zeckendorf::zeckendorf_big;
+
+create_exception!(
+    fibonacci_sequence,
+    FibonacciError,
+    PyValueError,
+    "An argument was rejected by input validation."
+);
+
+/// Convert a validation error into a Python exception
+fn error(error: InputError) -> PyErr {
+    match error {
+        // Like `%` does
+        InputError::ZeroModulus => PyZeroDivisionError::new_err(error.to_string()),
+        _ => FibonacciError::new_err(error.to_string()),
+    }
+}
+
+/// Validate an index for arbitrary-precision results
+fn index(n: &BigInt) -> PyResult<u64> {
+    validation::validate_input_with_max(&n.to_string(), MAX_BIG_N)
+        .map(u64::from)
+        .map_err(error)
+}
+
+/// Return F(n) for 0 <= n <= 100000.
+#[pyfunction]
+fn fib(py: Python<'_>, n: BigInt) -> PyResult<BigUint> {
+    let n = index(&n)?;
+    Ok(py.detach(|| fibonacci::fib_big(n)))
+}
+
+/// Return F(n) mod m for any n below 2**64, in O(log n).
+#[pyfunction]
+fn fib_mod(n: BigInt, m: BigInt) -> PyResult<u64> {
+    let n = validation::validate_value(&n.to_string()).map_err(error)?;
+    let m = validation::validate_modulus(&m.to_string()).map_err(error)?;
+    fibonacci::fib_mod(n, m).ok_or(InputError::ZeroModulus).map_err(error)
+}
+
+/// Return the period of the Fibonacci sequence mod m, for 1 <= m <= 10000000.
+#[pyfunction]
+fn pisano_period(py: Python<'_>, m: BigInt) -> PyResult<u64> {
+    let m = validation::validate_pisano_modulus(&m.to_string()).map_err(error)?;
+    py.detach(|| fibonacci::pisano_period(m))
+        .ok_or(InputError::ZeroModulus)
+        .map_err(error)
+}
+
+/// Return the distinct, non-consecutive Fibonacci numbers summing to x,
+/// largest first.
+#[pyfunction]
+fn zeckendorf(py: Python<'_>, x: BigInt) -> PyResult<Vec<BigUint>> {
+    let x = validation::validate_big_value(&x.to_string()).map_err(error)?;
+    Ok(py.detach(|| {
+        zeckendorf_big(&x)
+            .into_iter()
+            .map(|i| fibonacci::fib_big(i as u64))
+            .collect()
+    }))
+}
+
+/// Return whether x is a Fibonacci number. Negative numbers never are.
+#[pyfunction]
+fn is_fibonacci(py: Python<'_>, x: BigInt) -> PyResult<bool> {
+    if x < BigInt::ZERO {
+        return Ok(false);
+    }
+    let x = validation::validate_big_value(&x.to_string()).map_err(error)?;
+    Ok(py.detach(|| fibonacci::fibonacci_index_big(&x).is_some()))
+}
+
+/// Lazily yields F(start), F(start + 1), ... up to but excluding F(stop)
+#[pyclass(module = "fibonacci_sequence")]
+struct FibonacciIterator {
+    current: BigUint,
+    next: BigUint,
+    index: u64,
+    stop: Option<u64>,
+}
+
+#[pymethods]
+impl FibonacciIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<BigUint> {
+        if self.stop.is_some_and(|stop| self.index >= stop) {
+            return None;
+        }
+        let following = &self.current + &self.next;
+        let value = std::mem::replace(&mut self.current, std::mem::replace(&mut self.next, following));
+        self.index += 1;
+        Some(value)
+    }
+}
+
+/// Iterate over F(start), F(start + 1), ... up to but excluding F(stop), or
+/// forever when stop is None.
+#[pyfunction]
+#[pyo3(signature = (start = BigInt::ZERO, stop = None))]
+fn sequence(start: BigInt, stop: Option<BigInt>) -> PyResult<FibonacciIterator> {
+    let start = index(&start)?;
+    let stop = stop
+        .map(|stop| validation::validate_value(&stop.to_string()).map_err(error))
+        .transpose()?;
+    let (current, next) = fibonacci::fib_big_pair(start);
+    Ok(FibonacciIterator { current, next, index: start, stop })
+}
+
+#[pymodule]
+#[pyo3(name = "fibonacci_sequence")]
+fn fibonacci_python(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("FibonacciError", m.py().get_type::<FibonacciError>())?;
+    m.add_class::<FibonacciIterator>()?;
+    m.add_function(wrap_pyfunction!(fib, m)?)?;
+    m.add_function(wrap_pyfunction!(fib_mod, m)?)?;
+    m.add_function(wrap_pyfunction!(pisano_period, m)?)?;
+    m.add_function(wrap_pyfunction!(zeckendorf, m)?)?;
+    m.add_function(wrap_pyfunction!(is_fibonacci, m)?)?;
+    m.add_function(wrap_pyfunction!(sequence, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyDict;
+
+    /// Run Python source against the module bound to `fs`
+    fn run(source: &std::ffi::CStr) {
+        Python::attach(|py| {
+            let module = pyo3::wrap_pymodule!(fibonacci_python)(py);
+            let globals = PyDict::new(py);
+            globals.set_item("fs", module).unwrap();
+            if let Err(err) = py.run(source, Some(&globals), None) {
+                err.display(py);
+                panic!("Python assertion failed");
+            }
+        });
+    }
+
+    #[test]
+    fn test_values() {
+        run(c"
+assert fs.fib(10) == 55
+assert fs.fib(100) == 354224848179261915075
+assert fs.fib(100000) % 10**10 == 3428746875
+assert fs.fib_mod(10**12, 10) == 5
+assert fs.pisano_period(10) == 60
+assert fs.zeckendorf(100) == [89, 8, 3]
+assert fs.zeckendorf(0) == []
+assert fs.is_fibonacci(2**64) is False
+assert fs.is_fibonacci(fs.fib(500))
+assert fs.is_fibonacci(-1) is False
+");
+    }
+
+    #[test]
+    fn test_sequence() {
+        run(c"
+assert list(fs.sequence(stop=8)) == [0, 1, 1, 2, 3, 5, 8, 13]
+assert list(fs.sequence(98, 100)) == [fs.fib(98), fs.fib(99)]
+assert list(fs.sequence(5, 3)) == []
+it = iter(fs.sequence())
+assert [next(it) for _ in range(5)] == [0, 1, 1, 2, 3]
+assert isinstance(fs.sequence(), fs.FibonacciIterator)
+");
+    }
+
+    #[test]
+    fn test_errors() {
+        run(c"
+def raises(exception, f, *args):
+    try:
+        f(*args)
+    except exception as e:
+        return str(e)
+    raise AssertionError(f'{f.__name__}{args} did not raise {exception.__name__}')
+
+assert issubclass(fs.FibonacciError, ValueError)
+assert raises(fs.FibonacciError, fs.fib, 100001) == 'Number 100001 is too large! Please enter 0-100000'
+assert raises(fs.FibonacciError, fs.fib, -1) == 'Negative numbers are not supported! Please enter 0 or more'
+assert raises(fs.FibonacciError, fs.fib_mod, 2**64, 7)
+assert raises(ZeroDivisionError, fs.fib_mod, 5, 0) == 'Modulus must be at least 1'
+assert raises(ZeroDivisionError, fs.pisano_period, 0)
+assert raises(fs.FibonacciError, fs.fib_mod, 5, -7)
+assert 'too large' in raises(fs.FibonacciError, fs.pisano_period, 10**8)
+assert 'digits' in raises(fs.FibonacciError, fs.zeckendorf, 10**1000)
+assert raises(fs.FibonacciError, fs.sequence, 0, -1)
+assert raises(TypeError, fs.fib, 1.5)
+");
+    }
+}
@@ -1,22 +1,35 @@
+use fibonacci_sequence::error::Language;
+use fibonacci_sequence::fibonacci::Algorithm;
+use fibonacci_sequence::logging;
+use fibonacci_sequence::settings::Settings;
+use fibonacci_sequence::validation::validate_input_with_max;
 use gtk4::prelude::*;
 use gtk4::{glib, Application, ApplicationWindow, Box, Button, Entry, Label, Orientation, Frame};
+use tracing::{debug, info, info_span, warn};
 
 const APP_ID: &str = "org.fibonacci.alternative";
 
 fn main() -> glib::ExitCode {
+    // GTK parses the command line itself, so the filter comes from FIBONACCI_LOG
+    logging::init(None);
+    let settings = Settings::load_default().unwrap_or_else(|error| {
+        warn!(path = ?Settings::default_path(), %error, "using default settings");
+        Settings::default()
+    });
+
     let app = Application::builder().application_id(APP_ID).build();
-    app.connect_activate(build_alternative_ui);
+    app.connect_activate(move |app| build_alternative_ui(app, &settings));
     app.run()
 }
 
-fn build_alternative_ui(app: &Application) {
-    println!("Building alternative UI...");
+fn build_alternative_ui(app: &Application, settings: &Settings) {
+    let _span = info_span!("build_ui").entered();
     
     let window = ApplicationWindow::builder()
         .application(app)
         .title("Fibonacci Calculator - Alternative")
-        .default_width(500)
-        .default_height(400)
+        .default_width(settings.window.width as i32)
+        .default_height(settings.window.height as i32)
         .build();
 
     // Create main container with frame
@@ -35,16 +48,16 @@ fn build_alternative_ui(app: &Application) {
     input_box.set_margin_start(15);
     input_box.set_margin_end(15);
 
-    let instruction = Label::new(Some("Enter a number between 0 and 40:"));
+    let max_n = settings.limits.max_n.min(settings.algorithm.max_n());
+    let instruction = Label::new(Some(&format!("Enter a number between 0 and {}:", max_n)));
     
     let entry = Entry::new();
     entry.set_text("10"); // Pre-fill with a default value
     entry.set_width_chars(25);
     entry.set_alignment(0.5); // Center the text
     
-    // Add debugging
     entry.connect_changed(|entry| {
-        println!("Entry changed: '{}'", entry.text());
+        debug!(text = %entry.text(), "entry changed");
     });
     
     let button = Button::with_label("🧮 Calculate Fibonacci Number");
@@ -77,50 +90,18 @@ fn build_alternative_ui(app: &Application) {
     frame.set_child(Some(&main_box));
 
     // Set up event handlers
+    let algorithm = settings.algorithm;
     let entry_clone = entry.clone();
     let result_clone = result_label.clone();
-    
     button.connect_clicked(move |_| {
-        println!("Button clicked!");
-        let input_text = entry_clone.text();
-        println!("Processing input: '{}'", input_text);
-        
-        match input_text.parse::<u32>() {
-            Ok(n) if n <= 40 => {
-                let result = fib(n);
-                let text = format!("F({}) = {}\n\nThe {}th Fibonacci number is {}", n, result, n, result);
-                result_clone.set_text(&text);
-                println!("Result: F({}) = {}", n, result);
-            }
-            Ok(n) => {
-                result_clone.set_text(&format!("Number {} is too large!\nPlease enter a number between 0 and 40", n));
-            }
-            Err(_) => {
-                result_clone.set_text("Invalid input!\nPlease enter a valid number");
-            }
-        }
+        debug!("calculate button clicked");
+        show_result(&entry_clone, &result_clone, max_n, algorithm);
     });
 
     // Enter key handler
     entry.connect_activate(move |entry| {
-        println!("Enter key pressed!");
-        let input_text = entry.text();
-        println!("Processing input from Enter: '{}'", input_text);
-        
-        match input_text.parse::<u32>() {
-            Ok(n) if n <= 40 => {
-                let result = fib(n);
-                let text = format!("F({}) = {}\n\nThe {}th Fibonacci number is {}", n, result, n, result);
-                result_label.set_text(&text);
-                println!("Result: F({}) = {}", n, result);
-            }
-            Ok(n) => {
-                result_label.set_text(&format!("Number {} is too large!\nPlease enter a number between 0 and 40", n));
-            }
-            Err(_) => {
-                result_label.set_text("Invalid input!\nPlease enter a valid number");
-            }
-        }
+        debug!("enter pressed");
+        show_result(entry, &result_label, max_n, algorithm);
     });
 
     window.set_child(Some(&frame));
@@ -128,14 +109,26 @@ fn build_alternative_ui(app: &Application) {
     
     // Focus the entry field
     entry.grab_focus();
-    
-    println!("Alternative UI setup complete!");
+
+    info!("window ready");
 }
 
-fn fib(n: u32) -> u64 {
-    if n < 2 {
-        n as u64
-    } else {
-        fib(n - 1) + fib(n - 2)
+/// Validate the entry text and show F(n) or the validation error
+fn show_result(entry: &Entry, result_label: &Label, max_n: u32, algorithm: Algorithm) {
+    let input_text = entry.text();
+    let _span = info_span!("calculate", input = %input_text, algorithm = algorithm.name()).entered();
+
+    match validate_input_with_max(&input_text, max_n) {
+        Ok(n) => {
+            // n is at most the algorithm's limit, so F(n) is defined
+            let result = algorithm.nth(n).unwrap_or_default();
+            let text = format!("F({}) = {}\n\nThe {}th Fibonacci number is {}", n, result, n, result);
+            result_label.set_text(&text);
+            info!(n, result, "calculated");
+        }
+        Err(error) => {
+            warn!(%error, "rejected input");
+            result_label.set_text(&error.localized(Language::detect()).to_string());
+        }
     }
 }
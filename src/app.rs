@@ -1,6 +1,22 @@
+use std::sync::Arc;
+
 use eframe::egui;
-use crate::fibonacci;
-use crate::ui::{InputControls, ResultDisplay, SpiralVisualization, InstructionsPanel, validation};
+use tracing::{info, info_span, trace_span, warn};
+use crate::cache::FibCache;
+use crate::error::{InputError, Language};
+use crate::fibonacci::Algorithm;
+use crate::logging::LogBuffer;
+use crate::radix::Base;
+use crate::history::{History, HistoryEntry};
+use crate::session::{Layout, SAVE_INTERVAL, STORAGE_KEY, Session};
+use crate::settings::Settings;
+use crate::store::DiskCache;
+use crate::ui::{
+    ExportDialog, HistoryPanel, InputControls, InstructionsPanel, LogViewer, ProgressControls, ResultDisplay,
+    SettingsDialog, SpiralVisualization, validation,
+};
+use crate::visualization::utils::SequenceFormat;
+use crate::worker::Worker;
 
 /// Main application state
 #[derive(Default)]
@@ -13,45 +29,237 @@ pub struct FibonacciApp {
     fibonacci_sequence: Vec<u64>,
     /// Current n value
     current_n: u32,
+    /// Base used to show F(n)
+    display_base: Base,
+    /// Layout of the sequence text
+    sequence_format: SequenceFormat,
+    /// Language of validation messages
+    language: Language,
+    /// Limits, colors and defaults from the settings file
+    settings: Settings,
+    /// How F(n) is calculated, starting from the configured default
+    algorithm: Algorithm,
+    /// Finished calculations, listed in the history panel
+    history: History,
+    /// Values kept between calculations, shared with the worker
+    cache: Arc<FibCache>,
+    /// Computes the sequence off the UI thread
+    worker: Worker<(HistoryEntry, Vec<u64>)>,
     /// UI components
     spiral_visualization: SpiralVisualization,
+    export_dialog: ExportDialog,
+    log_viewer: LogViewer,
+    settings_dialog: SettingsDialog,
+    history_panel: HistoryPanel,
 }
 
 impl FibonacciApp {
-    /// Create a new Fibonacci application
+    /// Create a new Fibonacci application with the default settings
     pub fn new() -> Self {
-        Self {
+        Self::with_settings(Settings::default())
+    }
+
+    /// Create a new Fibonacci application, e.g. with settings read from the settings file
+    pub fn with_settings(settings: Settings) -> Self {
+        let mut app = Self {
             input_text: String::new(),
             result_text: String::new(),
             fibonacci_sequence: Vec::new(),
             current_n: 0,
+            display_base: Base::default(),
+            sequence_format: SequenceFormat::default(),
+            language: Language::detect(),
+            settings: Settings::default(),
+            algorithm: Algorithm::default(),
+            history: History::default(),
+            cache: Arc::new(Self::warm_cache(FibCache::default())),
+            worker: Worker::default(),
             spiral_visualization: SpiralVisualization::default(),
+            export_dialog: ExportDialog::default(),
+            log_viewer: LogViewer::default(),
+            settings_dialog: SettingsDialog::default(),
+            history_panel: HistoryPanel::default(),
+        };
+        app.apply_settings(settings);
+        app
+    }
+
+    /// Create the application as it was when last saved to `storage`
+    ///
+    /// Pass `CreationContext::storage`. Without a usable saved session this
+    /// is the same as [`FibonacciApp::with_settings`].
+    pub fn restore(storage: Option<&dyn eframe::Storage>, settings: Settings) -> Self {
+        let mut app = Self::with_settings(settings);
+        let Some(json) = storage.and_then(|storage| storage.get_string(STORAGE_KEY)) else {
+            return app;
+        };
+        match Session::from_json(&json) {
+            Ok(session) => {
+                info!(history = session.history.len(), "restored session");
+                app.apply_session(session);
+            }
+            Err(reason) => warn!(%reason, "ignoring saved session"),
         }
+        app
     }
 
-    /// Calculate Fibonacci sequence based on user input
-    fn calculate_fibonacci(&mut self) {
-        match validation::validate_input(&self.input_text) {
-            Ok(n) => {
-                let result = fibonacci::fib(n);
-                self.result_text = format!("F({}) = {}", n, result);
-                self.current_n = n;
+    /// The state saved between runs
+    fn session(&self) -> Session {
+        Session {
+            input: self.input_text.clone(),
+            history: self.history.clone(),
+            algorithm: Some(self.algorithm),
+            base: self.display_base,
+            sequence_format: self.sequence_format,
+            visualization: self.spiral_visualization.mode(),
+            layout: Layout {
+                export_open: self.export_dialog.open,
+                logs_open: self.log_viewer.open,
+                history_open: self.history_panel.open,
+            },
+            ..Session::default()
+        }
+    }
 
-                // Generate the sequence up to n using the more efficient iterative method
-                self.fibonacci_sequence = fibonacci::generate_sequence_iterative(n);
+    /// Bring back a saved session, showing its last result again
+    fn apply_session(&mut self, session: Session) {
+        if let Some(algorithm) = session.algorithm {
+            self.algorithm = algorithm;
+        }
+        self.display_base = session.base;
+        self.sequence_format = session.sequence_format;
+        self.spiral_visualization.set_mode(session.visualization);
+        self.export_dialog.open = session.layout.export_open;
+        self.log_viewer.open = session.layout.logs_open;
+        self.history_panel.open = session.layout.history_open;
+        self.input_text = session.input;
+        self.history = session.history;
+        // The cache holds every result that fits in a u64, so the last one is
+        // shown straight away instead of being calculated and recorded again
+        let max_n = self.settings.limits.max_n.min(self.algorithm.max_n());
+        if let Ok(n) = validation::validate_input_with_max(&self.input_text, max_n) {
+            let sequence = (0..=n).filter_map(|i| self.cache.get_u64(i)).collect();
+            self.show_sequence(n, sequence);
+        }
+    }
 
-                println!("Calculated F({}) = {}", n, result);
+    /// Use new settings from now on
+    fn apply_settings(&mut self, settings: Settings) {
+        let drawer = self.spiral_visualization.drawer_mut();
+        drawer.grid_size = settings.display.grid_size;
+        drawer.colors = settings
+            .display
+            .colors
+            .iter()
+            .map(|color| egui::Color32::from_rgb(color.0[0], color.0[1], color.0[2]))
+            .collect();
+        self.algorithm = settings.algorithm;
+        self.settings = settings;
+    }
+
+    /// Keep big results in the on-disk result cache, shared with `fib`
+    ///
+    /// The native binary passes the default directory. Without a store the
+    /// cache only lives in memory, as in the browser and in tests.
+    pub fn with_store(mut self, store: DiskCache) -> Self {
+        self.cache = Arc::new(Self::warm_cache(FibCache::default().with_store(store)));
+        self
+    }
+
+    /// `cache` holding every F(n) that fits in a u64
+    fn warm_cache(cache: FibCache) -> FibCache {
+        cache.warm(0..=validation::MAX_U64_N as u64);
+        cache
+    }
+
+    /// Validate the input and compute the sequence in the background,
+    /// superseding any calculation still running
+    fn calculate_fibonacci(&mut self) {
+        let max_n = self.settings.limits.max_n.min(self.algorithm.max_n());
+        match validation::validate_input_with_max(&self.input_text, max_n) {
+            Ok(n) => {
+                let cache = Arc::clone(&self.cache);
+                let algorithm = self.algorithm;
+                info!(n, algorithm = algorithm.name(), "calculating");
+                self.worker.start(move |progress| {
+                    let _span = info_span!("compute", n, algorithm = algorithm.name()).entered();
+                    let mut sequence = Vec::with_capacity(n as usize + 1);
+                    for i in 0..n {
+                        if progress.is_cancelled() {
+                            return None;
+                        }
+                        sequence.push(cache.get_u64(i)?);
+                        progress.set((i + 1) as f32 / (n + 1) as f32);
+                    }
+                    // F(n) itself comes from the chosen algorithm
+                    let value = algorithm.nth(n)?;
+                    sequence.push(value);
+                    Some((HistoryEntry::new(n, algorithm, value), sequence))
+                });
+                self.result_text = format!("Calculating F({})…", n);
             }
-            Err(error_msg) => {
-                self.result_text = error_msg;
+            Err(error) => {
+                warn!(input = %self.input_text, %error, "rejected input");
+                self.worker.cancel();
+                self.result_text = error.localized(self.language).to_string();
                 self.fibonacci_sequence.clear();
                 self.current_n = 0;
             }
         }
     }
 
+    /// Calculate as if the user entered `input`, waiting for the result
+    ///
+    /// The window calculates in the background instead; this is for driving
+    /// the app without one.
+    pub fn calculate(&mut self, input: &str) {
+        self.input_text = input.to_string();
+        self.calculate_fibonacci();
+        if let Some(result) = self.worker.wait() {
+            self.finish(result);
+        }
+    }
+
+    /// Show a finished calculation and add it to the history
+    fn finish(&mut self, (entry, sequence): (HistoryEntry, Vec<u64>)) {
+        self.history.record(entry);
+        self.show_sequence(entry.n, sequence);
+    }
+
+    /// Show a history entry again without recalculating or re-recording it
+    fn restore_entry(&mut self, entry: HistoryEntry) {
+        let mut sequence: Vec<u64> = (0..entry.n).filter_map(|i| self.cache.get_u64(i)).collect();
+        sequence.push(entry.value);
+
+        self.worker.cancel();
+        info!(n = entry.n, algorithm = entry.algorithm.name(), "restored history entry");
+        self.algorithm = entry.algorithm;
+        self.input_text = entry.n.to_string();
+        self.show_sequence(entry.n, sequence);
+    }
+
+    /// Show the sequence F(0) through F(n)
+    fn show_sequence(&mut self, n: u32, sequence: Vec<u64>) {
+        self.result_text = format!("F({}) = {}", n, sequence.last().copied().unwrap_or(0));
+        self.current_n = n;
+        self.fibonacci_sequence = sequence;
+    }
+
+    /// Show a sequence loaded from a file, as if F(n) had been calculated
+    pub fn load_sequence(&mut self, sequence: Vec<u64>) -> Result<(), InputError> {
+        let n = sequence.len().saturating_sub(1);
+        let n = validation::validate_input_with_max(&n.to_string(), self.settings.limits.max_n)?;
+
+        self.worker.cancel();
+        info!(n, "loaded sequence");
+        self.input_text = n.to_string();
+        self.show_sequence(n, sequence);
+        Ok(())
+    }
+
     /// Reset the application state
     pub fn reset(&mut self) {
+        self.worker.cancel();
         self.input_text.clear();
         self.result_text.clear();
         self.fibonacci_sequence.clear();
@@ -76,6 +284,17 @@ impl FibonacciApp {
 
 impl eframe::App for FibonacciApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let _span = trace_span!("frame").entered();
+        if let Some(result) = self.worker.poll() {
+            self.finish(result);
+        }
+
+        if self.history_panel.open
+            && let Some(entry) = self.history_panel.render(ctx, &mut self.history)
+        {
+            self.restore_entry(entry);
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             // Main heading
             ui.heading("🧮 Fibonacci Spiral Generator");
@@ -83,12 +302,28 @@ impl eframe::App for FibonacciApp {
 
             // Input section
             let mut should_calculate = false;
-            InputControls::render(ui, &mut self.input_text, &mut should_calculate);
+            InputControls::render(
+                ui,
+                &mut self.input_text,
+                self.settings.limits.max_n,
+                &mut self.algorithm,
+                &mut should_calculate,
+            );
 
             if should_calculate {
                 self.calculate_fibonacci();
             }
 
+            if let Some(fraction) = self.worker.progress() {
+                if ProgressControls::render(ui, fraction) {
+                    info!("calculation cancelled");
+                    self.worker.cancel();
+                    self.result_text = "Calculation cancelled".to_string();
+                }
+                // Keep polling the worker until it finishes
+                ctx.request_repaint();
+            }
+
             ui.add_space(10.0);
 
             // Result section
@@ -96,23 +331,24 @@ impl eframe::App for FibonacciApp {
 
             // Fibonacci Spiral Visualization
             if self.has_results() {
-                self.spiral_visualization.render(
-                    ui,
-                    &self.fibonacci_sequence,
-                    self.current_n,
-                );
+                let value = self.fibonacci_sequence.last().copied().unwrap_or(0);
+                ResultDisplay::render_in_base(ui, self.current_n, value, &mut self.display_base);
+
+                trace_span!("visualization", n = self.current_n).in_scope(|| {
+                    self.spiral_visualization.render(ui, &self.fibonacci_sequence, self.current_n);
+                });
 
                 ui.add_space(10.0);
 
                 // Show sequence numbers
-                ResultDisplay::render_sequence(ui, &self.fibonacci_sequence);
+                ResultDisplay::render_sequence(ui, &self.fibonacci_sequence, &mut self.sequence_format);
 
                 // Mathematical information
                 InstructionsPanel::render_math_info(ui, &self.fibonacci_sequence, self.current_n);
             }
 
             // Instructions and tips
-            InstructionsPanel::render(ui);
+            InstructionsPanel::render(ui, self.settings.limits.max_n);
 
             // Add a reset button at the bottom
             ui.add_space(10.0);
@@ -121,6 +357,22 @@ impl eframe::App for FibonacciApp {
                     self.reset();
                 }
 
+                if ui.button("📤 Export…").clicked() {
+                    self.export_dialog.open = true;
+                }
+
+                if ui.button("🕘 History").clicked() {
+                    self.history_panel.open = !self.history_panel.open;
+                }
+
+                if ui.button("📜 Logs").clicked() {
+                    self.log_viewer.open = true;
+                }
+
+                if ui.button("⚙ Settings").clicked() {
+                    self.settings_dialog.show(&self.settings);
+                }
+
                 ui.add_space(20.0);
 
                 // Show some statistics if we have results
@@ -134,15 +386,32 @@ impl eframe::App for FibonacciApp {
                 }
             });
         });
+
+        if let Some(sequence) = self.export_dialog.render(ctx, &self.fibonacci_sequence) {
+            let count = sequence.len();
+            let status = self
+                .load_sequence(sequence)
+                .map(|()| format!("Loaded {} terms", count))
+                .map_err(|error| error.localized(self.language).to_string());
+            self.export_dialog.set_status(status);
+        }
+
+        self.log_viewer.render(ctx, LogBuffer::global());
+
+        if self.settings_dialog.open
+            && let Some(settings) = self.settings_dialog.render(ctx, Settings::default_path().as_deref())
+        {
+            info!("settings saved");
+            self.apply_settings(settings);
+        }
     }
 
-    fn save(&mut self, _storage: &mut dyn eframe::Storage) {
-        // Storage functionality can be implemented later if needed
-        // For now, we'll skip persistent storage
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        storage.set_string(STORAGE_KEY, self.session().to_json());
     }
 
     fn auto_save_interval(&self) -> std::time::Duration {
-        std::time::Duration::from_secs(30)
+        SAVE_INTERVAL
     }
 }
 
@@ -196,6 +465,7 @@ pub mod config {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fibonacci;
 
     #[test]
     fn test_app_creation() {
@@ -205,11 +475,37 @@ mod tests {
         assert!(!app.has_results());
     }
 
+    #[test]
+    fn test_calculate_uses_cache() {
+        let mut app = FibonacciApp::new();
+        for input in ["10", "20", "10"] {
+            app.calculate(input);
+        }
+        assert_eq!(app.result_text, "F(10) = 55");
+        assert_eq!(app.get_sequence(), fibonacci::generate_sequence_iterative(10));
+        assert_eq!(app.cache.stats().misses, 0);
+    }
+
+    #[test]
+    fn test_with_store() {
+        use crate::store::{DEFAULT_MAX_BYTES, Key, MIN_STORED_N, Sequence};
+
+        let dir = std::env::temp_dir().join(format!("fibonacci_app_store_{}", std::process::id()));
+        let store = DiskCache::open(&dir, DEFAULT_MAX_BYTES).unwrap();
+        let mut app = FibonacciApp::new().with_store(store.clone());
+        app.calculate("20");
+        assert_eq!(app.result_text, "F(20) = 6765");
+
+        // Big values go through the same files `fib` uses
+        app.cache.get_big(MIN_STORED_N);
+        assert!(store.get(&Key::new(Sequence::Fibonacci, MIN_STORED_N)).is_some());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_app_reset() {
         let mut app = FibonacciApp::new();
-        app.input_text = "10".to_string();
-        app.calculate_fibonacci();
+        app.calculate("10");
         
         assert!(app.has_results());
         
@@ -217,4 +513,137 @@ mod tests {
         assert!(!app.has_results());
         assert!(app.input_text.is_empty());
     }
+
+    #[test]
+    fn test_calculation_runs_in_background() {
+        let mut app = FibonacciApp::new();
+        app.input_text = "5".to_string();
+        app.calculate_fibonacci();
+        assert_eq!(app.result_text, "Calculating F(5)…");
+
+        // A newer request supersedes the running one
+        app.calculate("7");
+        assert_eq!(app.result_text, "F(7) = 13");
+        assert_eq!(app.get_current_n(), 7);
+        assert!(!app.worker.is_running());
+
+        // Invalid input cancels and clears
+        app.input_text = "8".to_string();
+        app.calculate_fibonacci();
+        app.calculate("abc");
+        assert!(!app.worker.is_running());
+        assert!(!app.has_results());
+    }
+
+    #[test]
+    fn test_settings() {
+        let mut app = FibonacciApp::new();
+        assert_eq!(app.spiral_visualization.drawer_mut().colors, crate::visualization::FIBONACCI_COLORS);
+
+        let mut settings = Settings { algorithm: Algorithm::Memoized, ..Settings::default() };
+        settings.limits.max_n = 30;
+        settings.display.colors.truncate(2);
+        app = FibonacciApp::with_settings(settings);
+        assert_eq!(app.algorithm, Algorithm::Memoized);
+        assert_eq!(app.spiral_visualization.drawer_mut().colors.len(), 2);
+
+        app.calculate("30");
+        assert_eq!(app.result_text, "F(30) = 832040");
+        app.calculate("31");
+        assert_eq!(app.result_text, "Number 31 is too large! Please enter 0-30");
+
+        // The recursive algorithm caps the limit
+        app.algorithm = Algorithm::Recursive;
+        app.settings.limits.max_n = 93;
+        app.calculate("41");
+        assert_eq!(app.result_text, "Number 41 is too large! Please enter 0-40");
+    }
+
+    /// Storage kept in memory, like eframe's file or `localStorage`
+    #[derive(Default)]
+    struct MemoryStorage(std::collections::HashMap<String, String>);
+
+    impl eframe::Storage for MemoryStorage {
+        fn get_string(&self, key: &str) -> Option<String> {
+            self.0.get(key).cloned()
+        }
+
+        fn set_string(&mut self, key: &str, value: String) {
+            self.0.insert(key.to_string(), value);
+        }
+
+        fn flush(&mut self) {}
+    }
+
+    #[test]
+    fn test_session_is_restored() {
+        use eframe::{App as _, Storage as _};
+
+        let mut app = FibonacciApp::new();
+        app.algorithm = Algorithm::FastDoubling;
+        app.calculate("12");
+        // Times from an earlier run, which a new calculation would overwrite
+        for n in [9, 12] {
+            let entry = HistoryEntry::new(n, Algorithm::FastDoubling, fibonacci::fib_checked(n).unwrap());
+            app.history.record(HistoryEntry { time: 1_700_000_000, ..entry });
+        }
+        app.display_base = Base::Binary;
+        app.sequence_format = SequenceFormat::Values;
+        app.spiral_visualization.set_mode(crate::ui::VisualizationMode::Lattice);
+        app.log_viewer.open = true;
+
+        let mut storage = MemoryStorage::default();
+        app.save(&mut storage);
+        let restored = FibonacciApp::restore(Some(&storage), Settings::default());
+        assert!(!restored.worker.is_running());
+        assert_eq!(restored.input_text, "12");
+        assert_eq!(restored.result_text, "F(12) = 144");
+        assert_eq!(restored.get_sequence(), fibonacci::generate_sequence_iterative(12));
+        assert_eq!(restored.algorithm, Algorithm::FastDoubling);
+        // Showing the last result again is not a new calculation
+        assert_eq!(restored.history, app.history);
+        assert_eq!(restored.history.entries().iter().map(|entry| entry.n).collect::<Vec<_>>(), [9, 12]);
+        assert_eq!(restored.display_base, Base::Binary);
+        assert_eq!(restored.sequence_format, SequenceFormat::Values);
+        assert_eq!(restored.spiral_visualization.mode(), crate::ui::VisualizationMode::Lattice);
+        assert!(restored.log_viewer.open && !restored.export_dialog.open);
+
+        // Unusable saves start fresh
+        storage.set_string(STORAGE_KEY, r#"{"version":99}"#.to_string());
+        assert!(FibonacciApp::restore(Some(&storage), Settings::default()).history.is_empty());
+        assert!(FibonacciApp::restore(None, Settings::default()).input_text.is_empty());
+    }
+
+    #[test]
+    fn test_history() {
+        let mut app = FibonacciApp::new();
+        app.calculate("5");
+        app.algorithm = Algorithm::Memoized;
+        app.calculate("8");
+        app.calculate("abc");
+        assert_eq!(app.history.len(), 2);
+        assert_eq!(app.history.entries()[1].summary(), "F(8) = 21");
+
+        // Restoring shows the entry without recording it again
+        app.algorithm = Algorithm::Iterative;
+        app.restore_entry(app.history.entries()[1]);
+        assert_eq!(app.result_text, "F(8) = 21");
+        assert_eq!(app.input_text, "8");
+        assert_eq!(app.algorithm, Algorithm::Memoized);
+        assert_eq!(app.get_sequence(), fibonacci::generate_sequence_iterative(8));
+        assert_eq!(app.history.len(), 2);
+    }
+
+    #[test]
+    fn test_load_sequence() {
+        let mut app = FibonacciApp::new();
+        app.load_sequence(fibonacci::generate_sequence_iterative(12)).unwrap();
+        assert_eq!(app.get_current_n(), 12);
+        assert_eq!(app.input_text, "12");
+        assert_eq!(app.result_text, "F(12) = 144");
+
+        // Files longer than the display limit are rejected without touching the state
+        assert!(app.load_sequence(fibonacci::generate_sequence_iterative(40)).is_err());
+        assert_eq!(app.get_current_n(), 12);
+    }
 }
@@ -0,0 +1,23 @@
+use std::process::ExitCode;
+
+use fibonacci_sequence::server::Server;
+
+const DEFAULT_ADDR: &str = "127.0.0.1:8080";
+
+fn main() -> ExitCode {
+    let addr = std::env::args().nth(1).unwrap_or_else(|| DEFAULT_ADDR.to_string());
+
+    match Server::bind(&addr) {
+        Ok(server) => {
+            // Show the port actually bound, which differs from `addr` for port 0
+            let bound = server.local_addr().map_or(addr, |bound| bound.to_string());
+            eprintln!("Serving the Fibonacci API on http://{}/ (spec at /openapi.json)", bound);
+            server.run();
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: could not listen on {}: {}", addr, error);
+            ExitCode::FAILURE
+        }
+    }
+}
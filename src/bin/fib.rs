@@ -0,0 +1,22 @@
+use std::process::ExitCode;
+
+use clap::Parser;
+use fibonacci_sequence::cli::{self, Cli};
+use fibonacci_sequence::error::Language;
+use fibonacci_sequence::logging;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    logging::init(cli.log.as_deref());
+    match cli::run(&cli) {
+        Ok(output) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            tracing::warn!(%error, "command failed");
+            eprintln!("error: {}", cli::error_message(&error, Language::detect()));
+            ExitCode::FAILURE
+        }
+    }
+}
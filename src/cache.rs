@@ -0,0 +1,392 @@
+//! A thread-safe memo of Fibonacci numbers that outlives single calls.
+//!
+//! [`FibCache`] keeps recently used F(n) values up to a fixed number of
+//! entries and evicts the least recently used one when full. Values that fit
+//! store as `u64`; larger ones (with `bigint`) store as shared `BigUint`s, so
+//! a hit never copies digits. The cache is `Sync`: put it in an `Arc` or a
+//! `static` and use it from any thread.
+//!
+//! With the `store` feature a cache can be backed by a
+//! [`DiskCache`](crate::store::DiskCache), which keeps large values between runs.
+
+use std::collections::{BTreeMap, HashMap};
+use std::ops::RangeInclusive;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+#[cfg(feature = "bigint")]
+use std::sync::Arc;
+
+#[cfg(feature = "bigint")]
+use num_bigint::BigUint;
+
+use crate::fibonacci;
+use crate::hashing::BuildFibonacciHasher;
+#[cfg(feature = "store")]
+use crate::store::{DiskCache, Key, MIN_STORED_N, Sequence};
+#[cfg(feature = "bigint")]
+use crate::validation::MAX_BIG_N;
+use crate::validation::MAX_U64_N;
+
+/// Entries kept by `FibCache::default()`
+pub const DEFAULT_CAPACITY: usize = 1_024;
+
+/// Hit and eviction counters since the cache was created or cleared
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+/// A stored F(n)
+#[derive(Debug, Clone)]
+enum Stored {
+    Small(u64),
+    #[cfg(feature = "bigint")]
+    Big(Arc<BigUint>),
+}
+
+#[derive(Debug)]
+struct Slot {
+    value: Stored,
+    /// Tick of the last use, the key of this entry in `Inner::order`
+    used: u64,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    entries: HashMap<u64, Slot, BuildFibonacciHasher>,
+    /// n of every entry by last use, oldest first
+    order: BTreeMap<u64, u64>,
+    tick: u64,
+    stats: CacheStats,
+}
+
+impl Inner {
+    /// Look up F(n) and mark it as most recently used
+    fn get(&mut self, n: u64) -> Option<Stored> {
+        let slot = self.entries.get_mut(&n)?;
+        self.order.remove(&slot.used);
+        self.tick += 1;
+        slot.used = self.tick;
+        self.order.insert(self.tick, n);
+        Some(slot.value.clone())
+    }
+
+    fn insert(&mut self, n: u64, value: Stored, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        if let Some(slot) = self.entries.remove(&n) {
+            self.order.remove(&slot.used);
+        }
+        while self.entries.len() >= capacity {
+            let Some((_, oldest)) = self.order.pop_first() else { break };
+            self.entries.remove(&oldest);
+            self.stats.evictions += 1;
+        }
+        self.tick += 1;
+        self.entries.insert(n, Slot { value, used: self.tick });
+        self.order.insert(self.tick, n);
+    }
+}
+
+/// A shareable, size-limited memo of F(n)
+///
+/// # Examples
+/// ```
+/// use fibonacci_sequence::cache::FibCache;
+///
+/// let cache = FibCache::new(100);
+/// cache.warm(0..=50);
+/// assert_eq!(cache.get_u64(50), Some(12586269025));
+/// assert_eq!(cache.stats().hits, 1);
+/// ```
+#[derive(Debug)]
+pub struct FibCache {
+    inner: Mutex<Inner>,
+    capacity: usize,
+    #[cfg(feature = "store")]
+    store: Option<DiskCache>,
+}
+
+impl Default for FibCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl FibCache {
+    /// Create an empty cache holding at most `capacity` values
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::default(),
+            capacity,
+            #[cfg(feature = "store")]
+            store: None,
+        }
+    }
+
+    /// Read and write big values of at least `MIN_STORED_N` through `store`
+    #[cfg(feature = "store")]
+    pub fn with_store(mut self, store: DiskCache) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Most values kept at once
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of values currently cached
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Check if nothing is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hit, miss and eviction counts
+    pub fn stats(&self) -> CacheStats {
+        self.lock().stats
+    }
+
+    /// Drop every value and reset the counters
+    pub fn clear(&self) {
+        *self.lock() = Inner::default();
+    }
+
+    /// Get F(n), or `None` if it does not fit in a u64
+    pub fn get_u64(&self, n: u32) -> Option<u64> {
+        if n > MAX_U64_N {
+            return None;
+        }
+        if let Some(Stored::Small(value)) = self.lookup(n as u64) {
+            return Some(value);
+        }
+        let value = fibonacci::fib_checked(n)?;
+        self.lock().insert(n as u64, Stored::Small(value), self.capacity);
+        Some(value)
+    }
+
+    /// Get F(0) through F(n), or `None` if F(n) does not fit in a u64
+    pub fn sequence(&self, n: u32) -> Option<Vec<u64>> {
+        (0..=n).map(|i| self.get_u64(i)).collect()
+    }
+
+    /// Get F(n) as an arbitrary-precision integer
+    ///
+    /// A miss next to two cached neighbours costs one addition, so stepping n
+    /// up one at a time stays cheap even for large values. Other misses go to
+    /// the disk store, if there is one, before computing.
+    #[cfg(feature = "bigint")]
+    pub fn get_big(&self, n: u64) -> Arc<BigUint> {
+        if let Some(value) = u32::try_from(n).ok().and_then(|n| self.get_u64(n)) {
+            return Arc::new(BigUint::from(value));
+        }
+        match self.lookup(n) {
+            Some(Stored::Big(value)) => return value,
+            Some(Stored::Small(value)) => return Arc::new(BigUint::from(value)),
+            None => {}
+        }
+
+        let neighbours = {
+            let mut inner = self.lock();
+            inner.get(n - 1).zip(inner.get(n - 2))
+        };
+        let value = Arc::new(match neighbours {
+            Some((Stored::Big(a), Stored::Big(b))) => &*a + &*b,
+            Some((Stored::Big(a), Stored::Small(b))) => &*a + b,
+            _ => self.compute_big(n),
+        });
+        self.lock().insert(n, Stored::Big(value.clone()), self.capacity);
+        value
+    }
+
+    /// Compute and store every F(n) in `range`, without counting hits or misses
+    ///
+    /// Walks the range by additions, so warming is much cheaper than computing
+    /// each value on its own. Only the last `capacity` values of a longer range
+    /// are kept. The range stops at F([`MAX_BIG_N`]), or at F(93) without
+    /// `bigint`.
+    pub fn warm(&self, range: RangeInclusive<u64>) {
+        #[cfg(feature = "bigint")]
+        let end = (*range.end()).min(MAX_BIG_N as u64);
+        #[cfg(not(feature = "bigint"))]
+        let end = (*range.end()).min(MAX_U64_N as u64);
+        let start = (*range.start()).max((end + 1).saturating_sub(self.capacity as u64));
+        if start > end || self.capacity == 0 {
+            return;
+        }
+
+        #[cfg(feature = "bigint")]
+        {
+            let (mut a, mut b) = fibonacci::fib_big_pair(start);
+            for n in start..=end {
+                let value = match u64::try_from(&a) {
+                    Ok(value) => Stored::Small(value),
+                    Err(_) => Stored::Big(Arc::new(a.clone())),
+                };
+                self.lock().insert(n, value, self.capacity);
+                let next = &a + &b;
+                (a, b) = (b, next);
+            }
+        }
+        #[cfg(not(feature = "bigint"))]
+        {
+            for (n, value) in fibonacci::generate_sequence_iterative(end as u32).into_iter().enumerate() {
+                if n as u64 >= start {
+                    self.lock().insert(n as u64, Stored::Small(value), self.capacity);
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "bigint")]
+    fn compute_big(&self, n: u64) -> BigUint {
+        #[cfg(feature = "store")]
+        if let Some(store) = &self.store
+            && n >= MIN_STORED_N
+        {
+            return store.get_or_insert_with(&Key::new(Sequence::Fibonacci, n), || fibonacci::fib_big(n));
+        }
+        fibonacci::fib_big(n)
+    }
+
+    /// Look up F(n), counting a hit or a miss
+    fn lookup(&self, n: u64) -> Option<Stored> {
+        let mut inner = self.lock();
+        let value = inner.get(n);
+        if value.is_some() {
+            inner.stats.hits += 1;
+        } else {
+            inner.stats.misses += 1;
+        }
+        value
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        // Every update leaves `Inner` consistent, so a panicking holder is harmless
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_get_u64() {
+        let cache = FibCache::new(10);
+        assert_eq!(cache.get_u64(10), Some(55));
+        assert_eq!(cache.get_u64(10), Some(55));
+        assert_eq!(cache.get_u64(94), None);
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1, evictions: 0 });
+        assert_eq!(cache.sequence(5), Some(vec![0, 1, 1, 2, 3, 5]));
+        assert_eq!(cache.sequence(94), None);
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let cache = FibCache::new(3);
+        for n in [1, 2, 3] {
+            cache.get_u64(n);
+        }
+        // Touch 1 so 2 is the least recently used
+        cache.get_u64(1);
+        cache.get_u64(4);
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.stats().evictions, 1);
+
+        let misses = cache.stats().misses;
+        cache.get_u64(1);
+        cache.get_u64(2);
+        assert_eq!(cache.stats().misses, misses + 1);
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let cache = FibCache::new(0);
+        cache.warm(0..=10);
+        assert_eq!(cache.get_u64(10), Some(55));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_warm() {
+        let cache = FibCache::new(20);
+        cache.warm(0..=100);
+        assert_eq!(cache.len(), 20);
+        assert_eq!(cache.stats(), CacheStats::default());
+        assert_eq!(cache.get_u64(93), Some(12200160415121876738));
+        assert_eq!(cache.stats().hits, 1);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.stats(), CacheStats::default());
+    }
+
+    #[test]
+    fn test_warm_to_u64_max() {
+        // The end is clamped, so this neither overflows nor walks 2^64 terms
+        let cache = FibCache::new(3);
+        cache.warm(0..=u64::MAX);
+        assert_eq!(cache.len(), 3);
+        cache.warm(u64::MAX..=u64::MAX);
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn test_get_big() {
+        let cache = FibCache::new(100);
+        cache.warm(200..=210);
+        assert_eq!(*cache.get_big(210), fibonacci::fib_big(210));
+        // Neighbours 210 and 209 are cached, so 211 is one addition
+        assert_eq!(*cache.get_big(211), fibonacci::fib_big(211));
+        assert_eq!(*cache.get_big(1_000), fibonacci::fib_big(1_000));
+        assert_eq!(*cache.get_big(12), BigUint::from(144u32));
+        assert!(Arc::ptr_eq(&cache.get_big(1_000), &cache.get_big(1_000)));
+    }
+
+    #[test]
+    #[cfg(feature = "store")]
+    fn test_with_store() {
+        let dir = std::env::temp_dir().join(format!("fibonacci_cache_store_{}", std::process::id()));
+        let store = DiskCache::open(&dir, crate::store::DEFAULT_MAX_BYTES).unwrap();
+        let n = MIN_STORED_N + 1;
+
+        let cache = FibCache::new(10).with_store(store.clone());
+        assert_eq!(*cache.get_big(n), fibonacci::fib_big(n));
+        assert_eq!(store.get(&Key::new(Sequence::Fibonacci, n)), Some(fibonacci::fib_big(n)));
+        // Small values stay in memory only
+        cache.get_big(1_000);
+        assert_eq!(store.entries().unwrap().len(), 1);
+
+        // A fresh cache finds the value on disk
+        let cache = FibCache::new(10).with_store(store);
+        assert_eq!(*cache.get_big(n), fibonacci::fib_big(n));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_shared_between_threads() {
+        let cache = FibCache::new(64);
+        thread::scope(|scope| {
+            for offset in 0..4 {
+                let cache = &cache;
+                scope.spawn(move || {
+                    for n in (offset..=MAX_U64_N).step_by(4) {
+                        assert_eq!(cache.get_u64(n), fibonacci::fib_checked(n));
+                    }
+                });
+            }
+        });
+        assert!(cache.len() <= 64);
+        assert_eq!(cache.stats().misses, MAX_U64_N as u64 + 1);
+    }
+}
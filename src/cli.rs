@@ -0,0 +1,934 @@
+//! The `fib` command-line tool.
+//!
+//! Argument parsing lives here rather than in the binary so the commands can
+//! be tested without spawning processes. Numeric arguments are taken as text
+//! and checked with [`crate::validation`], so the CLI reports the same
+//! [`InputError`]s as the GUI, in the language of the user's locale.
+//! The default algorithm comes from the [`crate::settings`] file; problems
+//! with that file or the result cache are reported as a [`CliError`].
+
+use std::fmt::{self, Display};
+use std::io;
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use num_bigint::BigUint;
+
+use crate::error::{InputError, Language};
+use crate::fibonacci::Algorithm;
+use crate::settings::{Settings, SettingsError};
+use crate::store::{DEFAULT_MAX_BYTES, DiskCache, Key, MIN_STORED_N, Sequence};
+use crate::validation::{self, MAX_BIG_N, MAX_U64_N};
+use crate::{fibonacci, sums, zeckendorf};
+
+/// Fibonacci numbers from the command line
+#[derive(Debug, Parser)]
+#[command(name = "fib", version)]
+pub struct Cli {
+    /// Output format
+    #[arg(long, short, value_enum, default_value_t = Format::Plain, global = true)]
+    pub format: Format,
+
+    /// Use arbitrary-precision integers instead of u64
+    #[arg(long, global = true)]
+    pub big: bool,
+
+    /// Result cache directory [default: $FIBONACCI_CACHE_DIR or the platform cache directory]
+    #[arg(long, global = true, value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Neither read nor write cached results
+    #[arg(long, global = true)]
+    pub no_cache: bool,
+
+    /// Settings file [default: $FIBONACCI_CONFIG or the platform config directory]
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// How `nth` calculates u64 results [default: from the settings file]
+    #[arg(long, global = true, value_enum)]
+    pub algorithm: Option<Algorithm>,
+
+    /// Log filter such as `debug` or `fibonacci_sequence::store=trace` [default: $FIBONACCI_LOG or info]
+    #[arg(long, global = true, value_name = "FILTER")]
+    pub log: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+/// How results are printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Just the answer, for shell scripts
+    Plain,
+    /// A JSON object, or an array of objects for `seq`
+    Json,
+    /// A header line followed by one line per result
+    Csv,
+}
+
+/// The available subcommands
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Print F(n)
+    Nth { n: String },
+    /// Print F(from) through F(n)
+    Seq {
+        n: String,
+        /// First index to print
+        #[arg(long, default_value = "0")]
+        from: String,
+    },
+    /// Check whether a value is a Fibonacci number
+    Is { value: String },
+    /// Print the smallest n with F(n) equal to a value
+    Index { value: String },
+    /// Print F(n) mod m
+    Mod { n: String, m: String },
+    /// Print the Pisano period, the period of F(n) mod m
+    Pisano { m: String },
+    /// Write a value as a sum of non-consecutive Fibonacci numbers
+    Zeckendorf { value: String },
+    /// Print the Lucas number L(n)
+    Lucas { n: String },
+    /// Sum Fibonacci numbers over the index range from..=to
+    Sum {
+        from: String,
+        to: String,
+        /// Which terms to add up
+        #[arg(long, value_enum, default_value_t = SumKind::Values)]
+        of: SumKind,
+        /// Reduce the sum modulo m; indices may then be any u64
+        #[arg(long)]
+        modulo: Option<String>,
+    },
+    /// Inspect and clean up the on-disk result cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    /// Show the settings file and the settings in effect
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+/// What `config` does, see [`crate::settings`]
+#[derive(Debug, Subcommand)]
+pub enum ConfigAction {
+    /// Print where the settings file is read from
+    Path,
+    /// Print the settings in effect after validation
+    Show,
+}
+
+/// What `cache` does, see [`crate::store`]
+#[derive(Debug, Subcommand)]
+pub enum CacheAction {
+    /// List the cached results
+    List,
+    /// Check the format version and checksum of every entry
+    Verify,
+    /// Delete damaged and outdated entries, then the least recently used ones over a size
+    Prune {
+        /// Size to shrink the cache to, in bytes
+        #[arg(long, default_value_t = DEFAULT_MAX_BYTES)]
+        max_bytes: u64,
+        /// Delete every entry
+        #[arg(long, conflicts_with = "max_bytes")]
+        all: bool,
+    },
+}
+
+/// The aggregates offered by `sum`, see [`crate::sums`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SumKind {
+    /// F(i)
+    Values,
+    /// F(i)²
+    Squares,
+    /// F(i) for even i
+    Even,
+    /// F(i) for odd i
+    Odd,
+    /// (-1)^i F(i)
+    Alternating,
+    /// i·F(i)
+    Weighted,
+}
+
+/// Why a command failed
+#[derive(Debug)]
+pub enum CliError {
+    /// An argument was rejected
+    Input(InputError),
+    /// The result cache could not be used
+    Cache(io::Error),
+    /// The settings file could not be read or has invalid values
+    Settings { path: Option<PathBuf>, error: SettingsError },
+}
+
+impl From<InputError> for CliError {
+    fn from(error: InputError) -> Self {
+        CliError::Input(error)
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(error: io::Error) -> Self {
+        CliError::Cache(error)
+    }
+}
+
+impl From<SettingsError> for CliError {
+    fn from(error: SettingsError) -> Self {
+        CliError::Settings { path: None, error }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&localized(self, Language::English))
+    }
+}
+
+impl std::error::Error for CliError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CliError::Input(error) => Some(error),
+            CliError::Cache(error) => Some(error),
+            CliError::Settings { error, .. } => Some(error),
+        }
+    }
+}
+
+/// A single output field
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Null,
+    Number(String),
+    Bool(bool),
+    Text(String),
+    List(Vec<Value>),
+}
+
+impl Value {
+    fn number(n: impl Display) -> Self {
+        Value::Number(n.to_string())
+    }
+
+    fn to_json(&self) -> String {
+        match self {
+            Value::Null => "null".to_string(),
+            Value::Number(n) => n.clone(),
+            Value::Bool(b) => b.to_string(),
+            Value::Text(t) => json_string(t),
+            Value::List(items) => {
+                let items: Vec<String> = items.iter().map(Value::to_json).collect();
+                format!("[{}]", items.join(","))
+            }
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        match self {
+            Value::Null => String::new(),
+            Value::Number(n) => n.clone(),
+            Value::Bool(b) => b.to_string(),
+            Value::Text(t) if t.contains([',', '"', '\n']) => format!("\"{}\"", t.replace('"', "\"\"")),
+            Value::Text(t) => t.clone(),
+            Value::List(items) => {
+                let items: Vec<String> = items.iter().map(Value::to_csv).collect();
+                items.join(";")
+            }
+        }
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// The result of a command, ready to be rendered in any format
+struct Report {
+    columns: Vec<&'static str>,
+    rows: Vec<Vec<Value>>,
+    /// Whether JSON output is an array of rows rather than a single object
+    table: bool,
+    plain: String,
+}
+
+impl Report {
+    fn single(columns: Vec<&'static str>, row: Vec<Value>, plain: String) -> Self {
+        Self {
+            columns,
+            rows: vec![row],
+            table: false,
+            plain,
+        }
+    }
+
+    fn render(&self, format: Format) -> String {
+        match format {
+            Format::Plain => self.plain.clone(),
+            Format::Json => {
+                let objects: Vec<String> = self
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        let fields: Vec<String> = self
+                            .columns
+                            .iter()
+                            .zip(row)
+                            .map(|(column, value)| format!("{}:{}", json_string(column), value.to_json()))
+                            .collect();
+                        format!("{{{}}}", fields.join(","))
+                    })
+                    .collect();
+                if self.table {
+                    format!("[{}]", objects.join(","))
+                } else {
+                    objects.join("\n")
+                }
+            }
+            Format::Csv => {
+                let mut lines = vec![self.columns.join(",")];
+                lines.extend(self.rows.iter().map(|row| {
+                    row.iter().map(Value::to_csv).collect::<Vec<_>>().join(",")
+                }));
+                lines.join("\n")
+            }
+        }
+    }
+}
+
+/// Run a parsed command line and return the text to print
+///
+/// # Returns
+/// The rendered output without a trailing newline, or why the command failed
+pub fn run(cli: &Cli) -> Result<String, CliError> {
+    let _span = tracing::info_span!("run", command = ?cli.command, big = cli.big).entered();
+    let big = cli.big;
+    let report = match &cli.command {
+        Command::Nth { n } => nth(n, cli, &load_settings(cli)?)?,
+        Command::Seq { n, from } => seq(from, n, big)?,
+        Command::Is { value } => is(value, big)?,
+        Command::Index { value } => index(value, big)?,
+        Command::Mod { n, m } => modulo(n, m, cli)?,
+        Command::Pisano { m } => pisano(m)?,
+        Command::Zeckendorf { value } => zeckendorf(value, big)?,
+        Command::Lucas { n } => lucas(n, cli)?,
+        Command::Sum { from, to, of, modulo } => sum(from, to, *of, modulo.as_deref(), big)?,
+        Command::Cache { action } => cache(action, &open_cache(cli)?)?,
+        Command::Config { action } => config(action, cli)?,
+    };
+    Ok(report.render(cli.format))
+}
+
+/// Render an error from [`run`] in `language`, suggesting `--big` where it would help
+pub fn error_message(error: &CliError, language: Language) -> String {
+    let message = localized(error, language);
+    if !matches!(error, CliError::Input(error) if suggests_big(error)) {
+        return message;
+    }
+    let hint = match language {
+        Language::English => "use --big for larger values",
+        Language::Spanish => "usa --big para valores mayores",
+        Language::German => "nutze --big für größere Werte",
+    };
+    format!("{} ({})", message, hint)
+}
+
+/// The message for `error` in `language`, without hints
+fn localized(error: &CliError, language: Language) -> String {
+    let (what, detail) = match error {
+        CliError::Input(error) => return error.localized(language).to_string(),
+        CliError::Cache(error) => {
+            let what = match language {
+                Language::English => "Result cache error",
+                Language::Spanish => "Error de la caché de resultados",
+                Language::German => "Fehler im Ergebniscache",
+            };
+            (what, error.to_string())
+        }
+        CliError::Settings { path, error } => {
+            let what = match language {
+                Language::English => "Settings error",
+                Language::Spanish => "Error en la configuración",
+                Language::German => "Fehler in den Einstellungen",
+            };
+            let detail = match path {
+                Some(path) => format!("{}: {}", path.display(), error),
+                None => error.to_string(),
+            };
+            (what, detail)
+        }
+    };
+    format!("{}: {}", what, detail)
+}
+
+/// Whether the input behind `error` would have been accepted with `--big`
+fn suggests_big(error: &InputError) -> bool {
+    match error {
+        InputError::Overflow { .. } => true,
+        InputError::TooLarge { value, max } => {
+            *max == MAX_U64_N as u64 && validation::validate_input_with_max(value, MAX_BIG_N).is_ok()
+        }
+        _ => false,
+    }
+}
+
+/// `--config`, or the default settings file
+fn settings_path(cli: &Cli) -> Result<PathBuf, SettingsError> {
+    cli.config
+        .clone()
+        .or_else(Settings::default_path)
+        .ok_or_else(|| SettingsError::Io("no config directory on this platform".to_string()))
+}
+
+/// Read the settings file, or use the defaults if there is none
+fn load_settings(cli: &Cli) -> Result<Settings, CliError> {
+    match settings_path(cli) {
+        Ok(path) => Settings::load(&path).map_err(|error| CliError::Settings { path: Some(path), error }),
+        Err(_) => Ok(Settings::default()),
+    }
+}
+
+/// Open `--cache-dir`, or the default cache directory
+fn open_cache(cli: &Cli) -> io::Result<DiskCache> {
+    match &cli.cache_dir {
+        Some(dir) => DiskCache::open(dir, DEFAULT_MAX_BYTES),
+        None => DiskCache::open_default(),
+    }
+}
+
+/// Compute a `--big` or modular result, going through the result cache for large n
+///
+/// Caching is best effort: without a usable cache the value is just computed.
+fn stored(cli: &Cli, key: Key, compute: impl FnOnce() -> BigUint) -> BigUint {
+    if cli.no_cache || key.n < MIN_STORED_N {
+        return compute();
+    }
+    match open_cache(cli) {
+        Ok(cache) => cache.get_or_insert_with(&key, compute),
+        Err(_) => compute(),
+    }
+}
+
+/// Parse an index, allowing larger values in big-integer mode
+fn parse_index(input: &str, big: bool) -> Result<u32, InputError> {
+    validation::validate_input_with_max(input, if big { MAX_BIG_N } else { MAX_U64_N })
+}
+
+fn overflow(what: String) -> InputError {
+    InputError::Overflow { what }
+}
+
+fn nth(n: &str, cli: &Cli, settings: &Settings) -> Result<Report, InputError> {
+    let n = parse_index(n, cli.big)?;
+    let value = if cli.big {
+        let n = n as u64;
+        stored(cli, Key::new(Sequence::Fibonacci, n), || fibonacci::fib_big(n)).to_string()
+    } else {
+        let algorithm = cli.algorithm.unwrap_or(settings.algorithm);
+        if n > algorithm.max_n() {
+            return Err(InputError::TooLarge { value: n.to_string(), max: algorithm.max_n() as u64 });
+        }
+        algorithm.nth(n).ok_or_else(|| overflow(format!("F({})", n)))?.to_string()
+    };
+    Ok(Report::single(
+        vec!["n", "value"],
+        vec![Value::number(n), Value::Number(value.clone())],
+        value,
+    ))
+}
+
+fn seq(from: &str, n: &str, big: bool) -> Result<Report, InputError> {
+    parse_index(from, big)?;
+    parse_index(n, big)?;
+    let (from, n) = validation::validate_range(from, n, if big { MAX_BIG_N } else { MAX_U64_N })?;
+
+    let values: Vec<String> = if big {
+        let (mut a, mut b) = fibonacci::fib_big_pair(from as u64);
+        let mut values = Vec::with_capacity((n - from + 1) as usize);
+        for _ in from..=n {
+            values.push(a.to_string());
+            let next = &a + &b;
+            (a, b) = (b, next);
+        }
+        values
+    } else {
+        fibonacci::generate_sequence_iterative(n)[from as usize..]
+            .iter()
+            .map(u64::to_string)
+            .collect()
+    };
+
+    Ok(Report {
+        columns: vec!["n", "value"],
+        rows: (from..=n)
+            .zip(&values)
+            .map(|(i, value)| vec![Value::number(i), Value::Number(value.clone())])
+            .collect(),
+        table: true,
+        plain: values.join("\n"),
+    })
+}
+
+/// The Fibonacci index of a value, as (value, index) strings
+fn lookup(value: &str, big: bool) -> Result<(String, Option<String>), InputError> {
+    if big {
+        let value = validation::validate_big_value(value)?;
+        let index = fibonacci::fibonacci_index_big(&value);
+        Ok((value.to_string(), index.map(|i| i.to_string())))
+    } else {
+        let value = validation::validate_value(value)?;
+        let index = fibonacci::fibonacci_index(value);
+        Ok((value.to_string(), index.map(|i| i.to_string())))
+    }
+}
+
+fn is(value: &str, big: bool) -> Result<Report, InputError> {
+    let (value, index) = lookup(value, big)?;
+    let is_fibonacci = index.is_some();
+    Ok(Report::single(
+        vec!["value", "fibonacci"],
+        vec![Value::Number(value), Value::Bool(is_fibonacci)],
+        is_fibonacci.to_string(),
+    ))
+}
+
+fn index(value: &str, big: bool) -> Result<Report, InputError> {
+    let (value, index) = lookup(value, big)?;
+    let index = index.ok_or_else(|| InputError::NotFibonacci { value: value.clone() })?;
+    Ok(Report::single(
+        vec!["value", "index"],
+        vec![Value::Number(value), Value::Number(index.clone())],
+        index,
+    ))
+}
+
+fn modulo(n: &str, m: &str, cli: &Cli) -> Result<Report, InputError> {
+    let n = validation::validate_value(n)?;
+    let m = validation::validate_modulus(m)?;
+    let value = stored(cli, Key::modular(Sequence::Fibonacci, n, m), || {
+        BigUint::from(fibonacci::fib_mod(n, m).expect("validate_modulus rejects 0"))
+    });
+    Ok(Report::single(
+        vec!["n", "modulus", "value"],
+        vec![Value::number(n), Value::number(m), Value::number(&value)],
+        value.to_string(),
+    ))
+}
+
+fn pisano(m: &str) -> Result<Report, InputError> {
+    let m = validation::validate_pisano_modulus(m)?;
+    let period = fibonacci::pisano_period(m).ok_or(InputError::ZeroModulus)?;
+    Ok(Report::single(
+        vec!["modulus", "period"],
+        vec![Value::number(m), Value::number(period)],
+        period.to_string(),
+    ))
+}
+
+fn zeckendorf(value: &str, big: bool) -> Result<Report, InputError> {
+    let (value, indices, terms, digits) = if big {
+        let value = validation::validate_big_value(value)?;
+        let indices = zeckendorf::zeckendorf_big(&value);
+        let terms: Vec<String> = indices.iter().map(|&i| fibonacci::fib_big(i as u64).to_string()).collect();
+        let digits = zeckendorf::to_fibonacci_base_big(&value);
+        (value.to_string(), indices, terms, digits)
+    } else {
+        let value = validation::validate_value(value)?;
+        let indices = zeckendorf::zeckendorf(value);
+        let terms: Vec<String> = indices
+            .iter()
+            .map(|&i| fibonacci::fib_checked(i).map_or_else(String::new, |f| f.to_string()))
+            .collect();
+        (value.to_string(), indices, terms, zeckendorf::to_fibonacci_base(value))
+    };
+
+    let plain = if terms.is_empty() {
+        format!("{} = 0", value)
+    } else {
+        format!("{} = {}", value, terms.join(" + "))
+    };
+    Ok(Report::single(
+        vec!["value", "indices", "terms", "digits"],
+        vec![
+            Value::Number(value),
+            Value::List(indices.iter().map(Value::number).collect()),
+            Value::List(terms.into_iter().map(Value::Number).collect()),
+            Value::Text(digits),
+        ],
+        plain,
+    ))
+}
+
+fn lucas(n: &str, cli: &Cli) -> Result<Report, InputError> {
+    let n = parse_index(n, cli.big)?;
+    let value = if cli.big {
+        let n = n as u64;
+        stored(cli, Key::new(Sequence::Lucas, n), || fibonacci::lucas_big(n)).to_string()
+    } else {
+        fibonacci::lucas_checked(n).ok_or_else(|| overflow(format!("L({})", n)))?.to_string()
+    };
+    Ok(Report::single(
+        vec!["n", "value"],
+        vec![Value::number(n), Value::Number(value.clone())],
+        value,
+    ))
+}
+
+fn sum(from: &str, to: &str, kind: SumKind, modulus: Option<&str>, big: bool) -> Result<Report, InputError> {
+    let mut columns = vec!["from", "to", "value"];
+    let modulus = modulus.map(validation::validate_modulus).transpose()?;
+    let (from, to, value) = match modulus {
+        Some(m) => {
+            let (from, to) = (validation::validate_value(from)?, validation::validate_value(to)?);
+            let value = match kind {
+                SumKind::Values => sums::sum_range_mod(from, to, m),
+                SumKind::Squares => sums::sum_squares_mod(from, to, m),
+                SumKind::Even => sums::sum_even_index_mod(from, to, m),
+                SumKind::Odd => sums::sum_odd_index_mod(from, to, m),
+                SumKind::Alternating => sums::alternating_sum_mod(from, to, m),
+                SumKind::Weighted => sums::weighted_sum_mod(from, to, m),
+            };
+            columns.push("modulus");
+            (from, to, value.ok_or(InputError::ZeroModulus)?.to_string())
+        }
+        None if big => {
+            let (from, to) = (parse_index(from, true)? as u64, parse_index(to, true)? as u64);
+            let value = match kind {
+                SumKind::Values => sums::sum_range_big(from, to).to_string(),
+                SumKind::Squares => sums::sum_squares_big(from, to).to_string(),
+                SumKind::Even => sums::sum_even_index_big(from, to).to_string(),
+                SumKind::Odd => sums::sum_odd_index_big(from, to).to_string(),
+                SumKind::Alternating => sums::alternating_sum_big(from, to).to_string(),
+                SumKind::Weighted => sums::weighted_sum_big(from, to).to_string(),
+            };
+            (from, to, value)
+        }
+        None => {
+            let (from, to) = (parse_index(from, false)?, parse_index(to, false)?);
+            let value = match kind {
+                SumKind::Values => sums::sum_range_checked(from, to).map(|v| v.to_string()),
+                SumKind::Squares => sums::sum_squares_checked(from, to).map(|v| v.to_string()),
+                SumKind::Even => sums::sum_even_index_checked(from, to).map(|v| v.to_string()),
+                SumKind::Odd => sums::sum_odd_index_checked(from, to).map(|v| v.to_string()),
+                SumKind::Alternating => sums::alternating_sum_checked(from, to).map(|v| v.to_string()),
+                SumKind::Weighted => sums::weighted_sum_checked(from, to).map(|v| v.to_string()),
+            };
+            let value = value.ok_or_else(|| overflow(format!("The sum over {}..={}", from, to)))?;
+            (from as u64, to as u64, value)
+        }
+    };
+
+    let mut row = vec![Value::number(from), Value::number(to), Value::Number(value.clone())];
+    if let Some(m) = modulus {
+        row.push(Value::number(m));
+    }
+    Ok(Report::single(columns, row, value))
+}
+
+/// Only `show` reads the file, so `path` still works when it is broken
+fn config(action: &ConfigAction, cli: &Cli) -> Result<Report, CliError> {
+    Ok(match action {
+        ConfigAction::Path => {
+            let path = settings_path(cli)?;
+            Report::single(vec!["path"], vec![Value::Text(path.display().to_string())], path.display().to_string())
+        }
+        ConfigAction::Show => {
+            let settings = load_settings(cli)?;
+            Report::single(
+                vec!["algorithm", "max_n", "grid_size", "colors", "width", "height"],
+                vec![
+                    Value::Text(settings.algorithm.name().to_string()),
+                    Value::number(settings.limits.max_n),
+                    Value::number(settings.display.grid_size),
+                    Value::List(settings.display.colors.iter().map(|color| Value::Text(color.to_string())).collect()),
+                    Value::number(settings.window.width),
+                    Value::number(settings.window.height),
+                ],
+                settings.to_toml().trim_end().to_string(),
+            )
+        }
+    })
+}
+
+fn cache(action: &CacheAction, cache: &DiskCache) -> io::Result<Report> {
+    let modulus = |key: &Key| key.modulus.map_or(Value::Null, Value::number);
+    match action {
+        CacheAction::List => {
+            let entries = cache.entries()?;
+            Ok(Report {
+                columns: vec!["sequence", "n", "modulus", "bytes"],
+                rows: entries
+                    .iter()
+                    .map(|entry| {
+                        vec![
+                            Value::Text(entry.key.sequence.name().to_string()),
+                            Value::number(entry.key.n),
+                            modulus(&entry.key),
+                            Value::number(entry.bytes),
+                        ]
+                    })
+                    .collect(),
+                table: true,
+                plain: entries
+                    .iter()
+                    .map(|entry| format!("{}\t{} bytes", entry.key, entry.bytes))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            })
+        }
+        CacheAction::Verify => {
+            let statuses = cache.verify()?;
+            let mut plain: Vec<String> = statuses
+                .iter()
+                .filter_map(|(entry, status)| {
+                    let error = status.as_ref().err()?;
+                    Some(format!("{} {}", entry.path.display(), error))
+                })
+                .collect();
+            let bad = plain.len();
+            plain.push(format!("{} entries checked, {} unusable", statuses.len(), bad));
+            if bad > 0 {
+                plain.push("Run `fib cache prune` to delete them".to_string());
+            }
+            Ok(Report {
+                columns: vec!["sequence", "n", "modulus", "status"],
+                rows: statuses
+                    .iter()
+                    .map(|(entry, status)| {
+                        let status = match status {
+                            Ok(()) => "ok".to_string(),
+                            Err(error) => error.to_string(),
+                        };
+                        vec![
+                            Value::Text(entry.key.sequence.name().to_string()),
+                            Value::number(entry.key.n),
+                            modulus(&entry.key),
+                            Value::Text(status),
+                        ]
+                    })
+                    .collect(),
+                table: true,
+                plain: plain.join("\n"),
+            })
+        }
+        CacheAction::Prune { max_bytes, all } => {
+            let report = cache.prune(if *all { 0 } else { *max_bytes })?;
+            Ok(Report::single(
+                vec!["removed", "freed_bytes"],
+                vec![Value::number(report.removed), Value::number(report.freed)],
+                format!("Removed {} entries, freed {} bytes", report.removed, report.freed),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A settings file that does not exist, so tests see the defaults
+    fn no_settings() -> String {
+        std::env::temp_dir().join("fibonacci_cli_no_settings.toml").display().to_string()
+    }
+
+    /// Run `fib` with the default settings, keeping results out of the real
+    /// cache directory unless the test picks its own
+    fn fib(args: &[&str]) -> Result<String, String> {
+        let mut config = vec!["--config".to_string(), no_settings()];
+        if !args.contains(&"--cache-dir") {
+            config.push("--no-cache".to_string());
+        }
+        let args = args.iter().copied().chain(config.iter().map(String::as_str));
+        let cli = Cli::try_parse_from(std::iter::once("fib").chain(args)).map_err(|e| e.to_string())?;
+        run(&cli).map_err(|error| error_message(&error, Language::English))
+    }
+
+    #[test]
+    fn test_plain_output() {
+        assert_eq!(fib(&["nth", "10"]), Ok("55".to_string()));
+        assert_eq!(fib(&["seq", "5", "--from", "3"]), Ok("2\n3\n5".to_string()));
+        assert_eq!(fib(&["is", "144"]), Ok("true".to_string()));
+        assert_eq!(fib(&["is", "145"]), Ok("false".to_string()));
+        assert_eq!(fib(&["index", "6765"]), Ok("20".to_string()));
+        assert_eq!(fib(&["mod", "1000000000000", "10"]), Ok("5".to_string()));
+        assert_eq!(fib(&["pisano", "10"]), Ok("60".to_string()));
+        assert_eq!(fib(&["zeckendorf", "100"]), Ok("100 = 89 + 8 + 3".to_string()));
+        assert_eq!(fib(&["lucas", "10"]), Ok("123".to_string()));
+        assert_eq!(fib(&["sum", "0", "10"]), Ok("143".to_string()));
+        assert_eq!(fib(&["sum", "0", "9", "--of", "alternating"]), Ok("-22".to_string()));
+    }
+
+    #[test]
+    fn test_big_mode() {
+        assert_eq!(
+            fib(&["nth", "100", "--big"]),
+            Ok("354224848179261915075".to_string())
+        );
+        assert_eq!(fib(&["--big", "index", "354224848179261915075"]), Ok("100".to_string()));
+        assert_eq!(fib(&["is", "354224848179261915076", "--big"]), Ok("false".to_string()));
+        assert_eq!(
+            fib(&["sum", "0", "98", "--big"]),
+            Ok((fibonacci::fib_big(100) - 1u32).to_string())
+        );
+        assert_eq!(
+            fib(&["zeckendorf", "354224848179261915076", "--big"]),
+            Ok("354224848179261915076 = 354224848179261915075 + 1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_json_output() {
+        assert_eq!(fib(&["nth", "10", "--format", "json"]), Ok(r#"{"n":10,"value":55}"#.to_string()));
+        assert_eq!(
+            fib(&["seq", "2", "-f", "json"]),
+            Ok(r#"[{"n":0,"value":0},{"n":1,"value":1},{"n":2,"value":1}]"#.to_string())
+        );
+        assert_eq!(
+            fib(&["zeckendorf", "100", "-f", "json"]),
+            Ok(r#"{"value":100,"indices":[11,6,4],"terms":[89,8,3],"digits":"1000010100"}"#.to_string())
+        );
+        assert_eq!(
+            fib(&["sum", "0", "10", "--modulo", "7", "-f", "json"]),
+            Ok(r#"{"from":0,"to":10,"value":3,"modulus":7}"#.to_string())
+        );
+        // The modulus is echoed as parsed, not as typed
+        assert_eq!(
+            fib(&["sum", "0", "10", "--modulo", "007", "-f", "json"]),
+            Ok(r#"{"from":0,"to":10,"value":3,"modulus":7}"#.to_string())
+        );
+    }
+
+    #[test]
+    fn test_csv_output() {
+        assert_eq!(fib(&["seq", "2", "-f", "csv"]), Ok("n,value\n0,0\n1,1\n2,1".to_string()));
+        assert_eq!(fib(&["is", "8", "-f", "csv"]), Ok("value,fibonacci\n8,true".to_string()));
+        assert_eq!(
+            fib(&["zeckendorf", "100", "-f", "csv"]),
+            Ok("value,indices,terms,digits\n100,11;6;4,89;8;3,1000010100".to_string())
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            fib(&["nth", "100"]),
+            Err("Number 100 is too large! Please enter 0-93 (use --big for larger values)".to_string())
+        );
+        assert_eq!(fib(&["nth", "abc"]), Err("Please enter a valid number".to_string()));
+        assert_eq!(fib(&["index", "4"]), Err("4 is not a Fibonacci number".to_string()));
+        assert_eq!(fib(&["mod", "5", "0"]), Err("Modulus must be at least 1".to_string()));
+        assert_eq!(fib(&["seq", "3", "--from", "5"]), Err("Start 5 is after end 3".to_string()));
+        assert_eq!(fib(&["lucas", "93"]), Err("L(93) does not fit in a u64 (use --big for larger values)".to_string()));
+        assert!(fib(&["sum", "0", "93"]).unwrap_err().contains("does not fit"));
+        assert!(fib(&["pisano", "10000001"]).unwrap_err().contains("too large"));
+        assert_eq!(fib(&["nth", "100001", "--big"]), Err("Number 100001 is too large! Please enter 0-100000".to_string()));
+    }
+
+    #[test]
+    fn test_result_cache() {
+        let dir = std::env::temp_dir().join(format!("fibonacci_cli_cache_{}", std::process::id()));
+        let dir = dir.to_str().unwrap();
+        let _ = std::fs::remove_dir_all(dir);
+
+        let expected = fibonacci::fib_big(MIN_STORED_N).to_string();
+        assert_eq!(fib(&["nth", "10000", "--big", "--cache-dir", dir, "--no-cache"]), Ok(expected.clone()));
+        assert_eq!(fib(&["cache", "list", "--cache-dir", dir]), Ok(String::new()));
+        assert_eq!(fib(&["nth", "10000", "--big", "--cache-dir", dir]), Ok(expected.clone()));
+        assert_eq!(fib(&["nth", "10000", "--big", "--cache-dir", dir]), Ok(expected));
+        assert_eq!(fib(&["lucas", "100", "--big", "--cache-dir", dir]), Ok(fibonacci::lucas_big(100).to_string()));
+        assert_eq!(fib(&["mod", "1000000000000", "10", "--cache-dir", dir]), Ok("5".to_string()));
+        assert_eq!(fib(&["mod", "1000000000000", "10", "--cache-dir", dir]), Ok("5".to_string()));
+
+        let bytes = std::fs::metadata(format!("{}/fibonacci-10000.fib", dir)).unwrap().len();
+        let mod_bytes = std::fs::metadata(format!("{}/fibonacci-1000000000000-mod-10.fib", dir)).unwrap().len();
+        assert_eq!(
+            fib(&["cache", "list", "--cache-dir", dir]),
+            Ok(format!("F(10000)\t{} bytes\nF(1000000000000) mod 10\t{} bytes", bytes, mod_bytes))
+        );
+        assert_eq!(
+            fib(&["cache", "list", "-f", "json", "--cache-dir", dir]),
+            Ok(format!(
+                r#"[{{"sequence":"fibonacci","n":10000,"modulus":null,"bytes":{}}},{{"sequence":"fibonacci","n":1000000000000,"modulus":10,"bytes":{}}}]"#,
+                bytes, mod_bytes
+            ))
+        );
+        assert_eq!(fib(&["cache", "verify", "--cache-dir", dir]), Ok("2 entries checked, 0 unusable".to_string()));
+        assert_eq!(
+            fib(&["cache", "prune", "--all", "--cache-dir", dir]),
+            Ok(format!("Removed 2 entries, freed {} bytes", bytes + mod_bytes))
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_settings() {
+        let dir = std::env::temp_dir().join(format!("fibonacci_cli_settings_{}", std::process::id()));
+        let path = dir.join("config.toml");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let run_with = |args: &[&str]| {
+            let cli = Cli::try_parse_from(["fib", "--config", path.to_str().unwrap()].iter().chain(args)).unwrap();
+            run(&cli).map_err(|error| error.to_string())
+        };
+
+        std::fs::write(&path, "algorithm = \"recursive\"\n").unwrap();
+        assert_eq!(run_with(&["nth", "20"]), Ok("6765".to_string()));
+        assert_eq!(run_with(&["nth", "41"]), Err("Number 41 is too large! Please enter 0-40".to_string()));
+        assert_eq!(run_with(&["nth", "41", "--algorithm", "fast-doubling"]), Ok("165580141".to_string()));
+        assert_eq!(run_with(&["config", "path"]), Ok(path.display().to_string()));
+        assert!(run_with(&["config", "show"]).unwrap().contains("algorithm = \"recursive\""));
+        assert_eq!(
+            run_with(&["config", "show", "-f", "csv"]).unwrap().lines().next(),
+            Some("algorithm,max_n,grid_size,colors,width,height")
+        );
+
+        std::fs::write(&path, "[limits]\nmax_n = 0\n").unwrap();
+        assert_eq!(
+            run_with(&["nth", "5"]),
+            Err(format!("Settings error: {}: limits.max_n must be 1-93 with the iterative algorithm, not 0", path.display()))
+        );
+
+        // Only the commands that use the settings read the file
+        std::fs::write(&path, "[limits").unwrap();
+        assert!(run_with(&["nth", "5"]).unwrap_err().starts_with("Settings error: "));
+        assert!(run_with(&["config", "show"]).unwrap_err().starts_with("Settings error: "));
+        assert_eq!(run_with(&["config", "path"]), Ok(path.display().to_string()));
+        assert_eq!(run_with(&["pisano", "10"]), Ok("60".to_string()));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_localized_errors() {
+        let cli = Cli::try_parse_from(["fib", "nth", "100", "--config", &no_settings()]).unwrap();
+        let error = run(&cli).unwrap_err();
+        assert!(matches!(&error, CliError::Input(InputError::TooLarge { max: 93, .. })));
+        assert_eq!(
+            error_message(&error, Language::German),
+            "Die Zahl 100 ist zu groß! Bitte gib 0-93 ein (nutze --big für größere Werte)"
+        );
+        let error = CliError::from(InputError::NotFibonacci { value: "4".to_string() });
+        assert_eq!(error_message(&error, Language::Spanish), "4 no es un número de Fibonacci");
+        let error = CliError::from(io::Error::other("disk full"));
+        assert_eq!(error_message(&error, Language::German), "Fehler im Ergebniscache: disk full");
+    }
+}
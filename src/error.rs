@@ -0,0 +1,201 @@
+//! The error type shared by every frontend, with localized messages.
+//!
+//! [`crate::validation`] and the CLI, HTTP, egui, GTK and Python frontends all
+//! report rejected input as an [`InputError`]. `Display` renders the English
+//! message; [`InputError::localized`] renders it in another [`Language`].
+
+use alloc::string::String;
+use core::fmt;
+
+/// Why a piece of user input was rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputError {
+    /// Nothing but whitespace was entered
+    Empty,
+    /// The input is not a decimal number
+    NotANumber,
+    /// The input is a negative number
+    Negative,
+    /// The number is above the frontend's limit
+    TooLarge { value: String, max: u64 },
+    /// An arbitrary-precision value has too many digits
+    TooManyDigits { digits: usize, max: usize },
+    /// A modulus of zero
+    ZeroModulus,
+    /// A modulus above the limit for Pisano periods
+    ModulusTooLarge { value: u64, max: u64 },
+    /// A range whose start is after its end
+    ReversedRange { from: u32, to: u32 },
+    /// A range with more terms than one request may return
+    RangeTooLong { terms: u32, max: u32 },
+    /// A result does not fit in a u64, e.g. `what` is `"F(94)"`
+    Overflow { what: String },
+    /// A value passed where a Fibonacci number is required
+    NotFibonacci { value: String },
+    /// A required parameter was not given
+    MissingParameter { name: &'static str },
+}
+
+/// A language messages can be rendered in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    German,
+}
+
+impl Language {
+    /// Parse a locale such as `de_DE.UTF-8`, `es-MX` or `en`
+    ///
+    /// # Returns
+    /// `None` for `C`, `POSIX` and languages without translations
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let code = locale.split(['_', '-', '.', '@']).next()?;
+        match code.to_ascii_lowercase().as_str() {
+            "en" => Some(Self::English),
+            "es" => Some(Self::Spanish),
+            "de" => Some(Self::German),
+            _ => None,
+        }
+    }
+
+    /// The language of the user's locale, from `LC_ALL`, `LC_MESSAGES` or `LANG`
+    ///
+    /// Falls back to English when none is set or the language is not translated.
+    #[cfg(feature = "std")]
+    pub fn detect() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|locale| Self::from_locale(&locale))
+            .unwrap_or_default()
+    }
+}
+
+impl InputError {
+    /// Render the message in `language`
+    pub fn localized(&self, language: Language) -> Localized<'_> {
+        Localized { error: self, language }
+    }
+}
+
+/// An [`InputError`] rendered in a chosen [`Language`]
+#[derive(Debug, Clone, Copy)]
+pub struct Localized<'a> {
+    error: &'a InputError,
+    language: Language,
+}
+
+impl fmt::Display for Localized<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use InputError::*;
+        use Language::*;
+
+        match (self.error, self.language) {
+            (Empty, English) => write!(f, "Please enter a number"),
+            (Empty, Spanish) => write!(f, "Introduce un número"),
+            (Empty, German) => write!(f, "Bitte gib eine Zahl ein"),
+
+            (NotANumber, English) => write!(f, "Please enter a valid number"),
+            (NotANumber, Spanish) => write!(f, "Introduce un número válido"),
+            (NotANumber, German) => write!(f, "Bitte gib eine gültige Zahl ein"),
+
+            (Negative, English) => write!(f, "Negative numbers are not supported! Please enter 0 or more"),
+            (Negative, Spanish) => write!(f, "¡No se admiten números negativos! Introduce 0 o más"),
+            (Negative, German) => write!(f, "Negative Zahlen werden nicht unterstützt! Bitte gib 0 oder mehr ein"),
+
+            (TooLarge { value, max }, English) => write!(f, "Number {} is too large! Please enter 0-{}", value, max),
+            (TooLarge { value, max }, Spanish) => write!(f, "¡El número {} es demasiado grande! Introduce 0-{}", value, max),
+            (TooLarge { value, max }, German) => write!(f, "Die Zahl {} ist zu groß! Bitte gib 0-{} ein", value, max),
+
+            (TooManyDigits { digits, max }, English) => {
+                write!(f, "Number has {} digits! Please enter at most {}", digits, max)
+            }
+            (TooManyDigits { digits, max }, Spanish) => {
+                write!(f, "¡El número tiene {} cifras! Introduce como máximo {}", digits, max)
+            }
+            (TooManyDigits { digits, max }, German) => {
+                write!(f, "Die Zahl hat {} Stellen! Bitte gib höchstens {} ein", digits, max)
+            }
+
+            (ZeroModulus, English) => write!(f, "Modulus must be at least 1"),
+            (ZeroModulus, Spanish) => write!(f, "El módulo debe ser al menos 1"),
+            (ZeroModulus, German) => write!(f, "Der Modul muss mindestens 1 sein"),
+
+            (ModulusTooLarge { value, max }, English) => {
+                write!(f, "Modulus {} is too large! Please enter 1-{}", value, max)
+            }
+            (ModulusTooLarge { value, max }, Spanish) => {
+                write!(f, "¡El módulo {} es demasiado grande! Introduce 1-{}", value, max)
+            }
+            (ModulusTooLarge { value, max }, German) => {
+                write!(f, "Der Modul {} ist zu groß! Bitte gib 1-{} ein", value, max)
+            }
+
+            (ReversedRange { from, to }, English) => write!(f, "Start {} is after end {}", from, to),
+            (ReversedRange { from, to }, Spanish) => write!(f, "El inicio {} está después del final {}", from, to),
+            (ReversedRange { from, to }, German) => write!(f, "Der Anfang {} liegt nach dem Ende {}", from, to),
+
+            (RangeTooLong { terms, max }, English) => {
+                write!(f, "Range has {} terms! Please request at most {}", terms, max)
+            }
+            (RangeTooLong { terms, max }, Spanish) => {
+                write!(f, "¡El rango tiene {} términos! Pide como máximo {}", terms, max)
+            }
+            (RangeTooLong { terms, max }, German) => {
+                write!(f, "Der Bereich hat {} Glieder! Bitte fordere höchstens {} an", terms, max)
+            }
+
+            (Overflow { what }, English) => write!(f, "{} does not fit in a u64", what),
+            (Overflow { what }, Spanish) => write!(f, "{} no cabe en un u64", what),
+            (Overflow { what }, German) => write!(f, "{} passt nicht in einen u64", what),
+
+            (NotFibonacci { value }, English) => write!(f, "{} is not a Fibonacci number", value),
+            (NotFibonacci { value }, Spanish) => write!(f, "{} no es un número de Fibonacci", value),
+            (NotFibonacci { value }, German) => write!(f, "{} ist keine Fibonacci-Zahl", value),
+
+            (MissingParameter { name }, English) => write!(f, "Missing query parameter '{}'", name),
+            (MissingParameter { name }, Spanish) => write!(f, "Falta el parámetro de consulta '{}'", name),
+            (MissingParameter { name }, German) => write!(f, "Der Abfrageparameter '{}' fehlt", name),
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.localized(Language::English).fmt(f)
+    }
+}
+
+impl core::error::Error for InputError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_display_is_english() {
+        let error = InputError::TooLarge { value: "26".to_string(), max: 25 };
+        assert_eq!(error.to_string(), "Number 26 is too large! Please enter 0-25");
+        assert_eq!(error.to_string(), error.localized(Language::English).to_string());
+    }
+
+    #[test]
+    fn test_localized() {
+        let error = InputError::ReversedRange { from: 5, to: 3 };
+        assert_eq!(error.localized(Language::Spanish).to_string(), "El inicio 5 está después del final 3");
+        assert_eq!(error.localized(Language::German).to_string(), "Der Anfang 5 liegt nach dem Ende 3");
+    }
+
+    #[test]
+    fn test_from_locale() {
+        assert_eq!(Language::from_locale("de_DE.UTF-8"), Some(Language::German));
+        assert_eq!(Language::from_locale("es-MX"), Some(Language::Spanish));
+        assert_eq!(Language::from_locale("EN"), Some(Language::English));
+        assert_eq!(Language::from_locale("C"), None);
+        assert_eq!(Language::from_locale(""), None);
+    }
+}
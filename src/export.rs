@@ -0,0 +1,320 @@
+//! Export and import of Fibonacci sequences as CSV, JSON and NDJSON.
+//!
+//! Every row carries the term and a few derived analytics columns. Only
+//! `index` and `value` are read back on import; the derived columns are
+//! recomputed, so hand-edited files cannot smuggle in inconsistent ratios.
+//!
+//! In the browser there is no file system: on `wasm32` a "file" is an entry in
+//! `localStorage` keyed by its path.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::fibonacci;
+
+/// Whether a term is even or odd
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Parity {
+    Even,
+    Odd,
+}
+
+/// One exported term with its analytics columns
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SequenceRow {
+    /// n in F(n)
+    pub index: u32,
+    /// F(n)
+    pub value: u64,
+    /// F(n) / F(n-1), absent for the first term and after F(0) = 0
+    #[serde(default)]
+    pub ratio: Option<f64>,
+    /// Number of decimal digits of F(n)
+    #[serde(default)]
+    pub digits: u32,
+    /// Parity of F(n)
+    #[serde(default = "default_parity")]
+    pub parity: Parity,
+}
+
+fn default_parity() -> Parity {
+    Parity::Even
+}
+
+/// Supported file formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    /// A single JSON array of rows
+    Json,
+    /// Newline-delimited JSON, one row object per line
+    Ndjson,
+}
+
+impl ExportFormat {
+    /// All formats, in display order
+    pub const ALL: [ExportFormat; 3] = [ExportFormat::Csv, ExportFormat::Json, ExportFormat::Ndjson];
+
+    /// Short label for selector buttons
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Json => "JSON",
+            ExportFormat::Ndjson => "NDJSON",
+        }
+    }
+
+    /// Conventional file extension, without the dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Ndjson => "ndjson",
+        }
+    }
+
+    /// Guess the format from a file name's extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            "ndjson" | "jsonl" => Some(ExportFormat::Ndjson),
+            _ => None,
+        }
+    }
+}
+
+/// Build the export rows for a sequence starting at F(0)
+pub fn rows(sequence: &[u64]) -> Vec<SequenceRow> {
+    sequence
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| SequenceRow {
+            index: i as u32,
+            value,
+            ratio: i
+                .checked_sub(1)
+                .map(|prev| sequence[prev])
+                .filter(|&prev| prev != 0)
+                .map(|prev| value as f64 / prev as f64),
+            digits: value.checked_ilog10().map_or(1, |d| d + 1),
+            parity: if value.is_multiple_of(2) { Parity::Even } else { Parity::Odd },
+        })
+        .collect()
+}
+
+/// Serialize a sequence starting at F(0)
+pub fn export(sequence: &[u64], format: ExportFormat) -> Result<String, String> {
+    serialize(&rows(sequence), format)
+}
+
+/// Serialize any rows, one CSV line, JSON array element or NDJSON line each
+pub fn serialize<T: Serialize>(rows: &[T], format: ExportFormat) -> Result<String, String> {
+    match format {
+        ExportFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            for row in rows {
+                writer.serialize(row).map_err(|e| format!("Could not write CSV: {}", e))?;
+            }
+            let bytes = writer.into_inner().map_err(|e| format!("Could not write CSV: {}", e))?;
+            String::from_utf8(bytes).map_err(|e| format!("Could not write CSV: {}", e))
+        }
+        ExportFormat::Json => serde_json::to_string_pretty(rows).map_err(|e| format!("Could not write JSON: {}", e)),
+        ExportFormat::Ndjson => {
+            let mut out = String::new();
+            for row in rows {
+                out.push_str(&serde_json::to_string(row).map_err(|e| format!("Could not write JSON: {}", e))?);
+                out.push('\n');
+            }
+            Ok(out)
+        }
+    }
+}
+
+/// Parse exported rows back into a sequence
+///
+/// # Returns
+/// F(0) through F(n), or an error if the text is malformed, the indices do
+/// not run 0, 1, 2, ... or a value is not the Fibonacci number at its index
+pub fn import(text: &str, format: ExportFormat) -> Result<Vec<u64>, String> {
+    let rows: Vec<SequenceRow> = match format {
+        ExportFormat::Csv => csv::Reader::from_reader(text.as_bytes())
+            .deserialize()
+            .enumerate()
+            .map(|(i, row)| row.map_err(|e| format!("Row {}: {}", i + 1, e)))
+            .collect::<Result<_, _>>()?,
+        ExportFormat::Json => serde_json::from_str(text).map_err(|e| format!("Invalid JSON: {}", e))?,
+        ExportFormat::Ndjson => text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| serde_json::from_str(line).map_err(|e| format!("Line {}: {}", i + 1, e)))
+            .collect::<Result<_, _>>()?,
+    };
+
+    if rows.is_empty() {
+        return Err("The file contains no terms".to_string());
+    }
+
+    rows.iter()
+        .enumerate()
+        .map(|(i, row)| {
+            if row.index as usize != i {
+                return Err(format!("Expected index {} but found {}", i, row.index));
+            }
+            if fibonacci::fib_checked(row.index) != Some(row.value) {
+                return Err(format!("{} is not F({})", row.value, row.index));
+            }
+            Ok(row.value)
+        })
+        .collect()
+}
+
+/// The format for a file, from its extension, or why there is none
+pub fn format_for_path(path: &Path) -> Result<ExportFormat, String> {
+    ExportFormat::from_path(path)
+        .ok_or_else(|| format!("Unknown file type for {}; use .csv, .json or .ndjson", path.display()))
+}
+
+/// Write a sequence to a file, choosing the format from its extension
+pub fn export_to_file(path: &Path, sequence: &[u64]) -> Result<(), String> {
+    let text = export(sequence, format_for_path(path)?)?;
+    write_text(path, &text)
+}
+
+/// Read a sequence from a file, choosing the format from its extension
+pub fn import_from_file(path: &Path) -> Result<Vec<u64>, String> {
+    let format = format_for_path(path)?;
+    let text = read_text(path)?;
+    import(&text, format)
+}
+
+/// Write `text` to a file, or to browser storage on wasm32
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn write_text(path: &Path, text: &str) -> Result<(), String> {
+    std::fs::write(path, text).map_err(|e| format!("Could not write {}: {}", path.display(), e))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_text(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path.display(), e))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Result<web_sys::Storage, String> {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .ok_or_else(|| "Browser storage is not available".to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn write_text(path: &Path, text: &str) -> Result<(), String> {
+    local_storage()?
+        .set_item(&path.display().to_string(), text)
+        .map_err(|_| format!("Could not write {}: browser storage is full", path.display()))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_text(path: &Path) -> Result<String, String> {
+    local_storage()?
+        .get_item(&path.display().to_string())
+        .ok()
+        .flatten()
+        .ok_or_else(|| format!("Could not read {}: not found in browser storage", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rows() {
+        let rows = rows(&fibonacci::generate_sequence_iterative(13));
+        assert_eq!(rows[0].ratio, None);
+        assert_eq!(rows[1].ratio, None);
+        assert_eq!(rows[2].ratio, Some(1.0));
+        assert_eq!(rows[13].value, 233);
+        assert_eq!(rows[13].digits, 3);
+        assert_eq!(rows[0].digits, 1);
+        assert_eq!(rows[12].parity, Parity::Even);
+        assert_eq!(rows[13].parity, Parity::Odd);
+        assert!((rows[13].ratio.unwrap() - fibonacci::PHI).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_csv_layout() {
+        let csv = export(&[0, 1, 1, 2], ExportFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "index,value,ratio,digits,parity");
+        assert_eq!(lines[1], "0,0,,1,even");
+        assert_eq!(lines[4], "3,2,2.0,1,even");
+    }
+
+    #[test]
+    fn test_ndjson_layout() {
+        let ndjson = export(&[0, 1], ExportFormat::Ndjson).unwrap();
+        assert_eq!(
+            ndjson,
+            "{\"index\":0,\"value\":0,\"ratio\":null,\"digits\":1,\"parity\":\"even\"}\n\
+             {\"index\":1,\"value\":1,\"ratio\":null,\"digits\":1,\"parity\":\"odd\"}\n"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for n in [0, 1, 2, 25, 93] {
+            let sequence = fibonacci::generate_sequence_iterative(n);
+            for format in ExportFormat::ALL {
+                let text = export(&sequence, format).unwrap();
+                assert_eq!(import(&text, format), Ok(sequence.clone()), "{:?} n = {}", format, n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_import_minimal_columns() {
+        assert_eq!(import("index,value\n0,0\n1,1\n2,1\n", ExportFormat::Csv), Ok(vec![0, 1, 1]));
+        assert_eq!(import(r#"[{"index":0,"value":0}]"#, ExportFormat::Json), Ok(vec![0]));
+    }
+
+    #[test]
+    fn test_import_errors() {
+        assert!(import("", ExportFormat::Json).is_err());
+        assert!(import("index,value\n", ExportFormat::Csv).is_err());
+        assert!(import("index,value\n0,zero\n", ExportFormat::Csv).is_err());
+        assert_eq!(
+            import("index,value\n0,0\n2,1\n", ExportFormat::Csv),
+            Err("Expected index 1 but found 2".to_string())
+        );
+        assert_eq!(
+            import("{\"index\":0,\"value\":7}\n", ExportFormat::Ndjson),
+            Err("7 is not F(0)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(ExportFormat::from_path(Path::new("seq.CSV")), Some(ExportFormat::Csv));
+        assert_eq!(ExportFormat::from_path(Path::new("seq.jsonl")), Some(ExportFormat::Ndjson));
+        assert_eq!(ExportFormat::from_path(Path::new("seq.txt")), None);
+        assert_eq!(ExportFormat::from_path(Path::new("seq")), None);
+        assert_eq!(format_for_path(Path::new("seq.ndjson")), Ok(ExportFormat::Ndjson));
+        assert_eq!(
+            format_for_path(Path::new("seq.txt")),
+            Err("Unknown file type for seq.txt; use .csv, .json or .ndjson".to_string())
+        );
+    }
+
+    #[test]
+    fn test_file_round_trip() {
+        let path = std::env::temp_dir().join(format!("fibonacci_export_{}.ndjson", std::process::id()));
+        let sequence = fibonacci::generate_sequence_iterative(20);
+        export_to_file(&path, &sequence).unwrap();
+        assert_eq!(import_from_file(&path), Ok(sequence));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,3 +1,13 @@
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "bigint")]
+use num_bigint::BigUint;
+#[cfg(feature = "bigint")]
+use num_traits::{One, Zero};
+
+/// The golden ratio φ
+pub const PHI: f64 = 1.618_033_988_749_895;
+
 /// Calculate the nth Fibonacci number using recursion
 /// 
 /// # Arguments
@@ -29,6 +39,7 @@ pub fn fib(n: u32) -> u64 {
 /// 
 /// # Returns
 /// A vector containing the Fibonacci sequence from F(0) to F(n)
+#[cfg(feature = "alloc")]
 pub fn generate_sequence(n: u32) -> Vec<u64> {
     let mut sequence = Vec::with_capacity((n + 1) as usize);
     for i in 0..=n {
@@ -39,9 +50,14 @@ pub fn generate_sequence(n: u32) -> Vec<u64> {
 
 /// Calculate Fibonacci number using memoization for better performance
 /// This is more efficient for larger numbers but uses more memory
+///
+/// The memo is dropped when the call returns; `cache::FibCache` keeps values
+/// between calls.
+#[cfg(feature = "alloc")]
 pub fn fib_memoized(n: u32) -> u64 {
-    fn fib_memo_helper(n: u32, memo: &mut std::collections::HashMap<u32, u64>) -> u64 {
-        if let Some(&result) = memo.get(&n) {
+    // Every index below n is visited, so a dense table beats a map
+    fn fib_memo_helper(n: u32, memo: &mut [Option<u64>]) -> u64 {
+        if let Some(result) = memo[n as usize] {
             return result;
         }
         
@@ -51,15 +67,16 @@ pub fn fib_memoized(n: u32) -> u64 {
             fib_memo_helper(n - 1, memo) + fib_memo_helper(n - 2, memo)
         };
         
-        memo.insert(n, result);
+        memo[n as usize] = Some(result);
         result
     }
     
-    let mut memo = std::collections::HashMap::new();
+    let mut memo = vec![None; n as usize + 1];
     fib_memo_helper(n, &mut memo)
 }
 
 /// Generate Fibonacci sequence using iterative approach (most efficient)
+#[cfg(feature = "alloc")]
 pub fn generate_sequence_iterative(n: u32) -> Vec<u64> {
     if n == 0 {
         return vec![0];
@@ -82,13 +99,261 @@ pub fn generate_sequence_iterative(n: u32) -> Vec<u64> {
 
 /// Check if a number is a Fibonacci number
 pub fn is_fibonacci_number(num: u64) -> bool {
-    // A number is Fibonacci if one of (5*n^2 + 4) or (5*n^2 - 4) is a perfect square
-    fn is_perfect_square(n: u64) -> bool {
-        let sqrt = (n as f64).sqrt() as u64;
+    // A number is Fibonacci if one of (5*n^2 + 4) or (5*n^2 - 4) is a perfect square.
+    // An integer square root stays exact where a float one would round
+    fn is_perfect_square(n: u128) -> bool {
+        let sqrt = n.isqrt();
         sqrt * sqrt == n
     }
     
-    is_perfect_square(5 * num * num + 4) || is_perfect_square(5 * num * num - 4)
+    let Some(plus) = (num as u128 * num as u128).checked_mul(5).and_then(|x| x.checked_add(4)) else {
+        // 5*n^2 only overflows a u128 above F(92), where F(93) is the one candidate
+        return fib_checked(93) == Some(num);
+    };
+    is_perfect_square(plus) || (num > 0 && is_perfect_square(plus - 8))
+}
+
+/// Calculate F(n) as a u128 using fast doubling, or `None` if it overflows
+///
+/// Runs in O(log n) using F(2k) = F(k)(2F(k+1) - F(k)) and F(2k+1) = F(k)² + F(k+1)².
+/// F(186) is the largest Fibonacci number that fits in a u128.
+pub fn fib_u128(n: u32) -> Option<u128> {
+    // (F(m), F(m+1)) for m = n / 2; F(94) is far from the u128 limit
+    fn pair(m: u32) -> (u128, u128) {
+        if m == 0 {
+            return (0, 1);
+        }
+        let (a, b) = pair(m / 2);
+        let c = a * (2 * b - a);
+        let d = a * a + b * b;
+        if m.is_multiple_of(2) { (c, d) } else { (d, c + d) }
+    }
+
+    if n > 186 {
+        return None;
+    }
+    let (a, b) = pair(n / 2);
+    if n.is_multiple_of(2) {
+        Some(a * (2 * b - a))
+    } else {
+        Some(a * a + b * b)
+    }
+}
+
+/// Calculate F(n) in O(log n), or `None` if it does not fit in a u64
+///
+/// # Examples
+/// ```
+/// use fibonacci_sequence::fibonacci::fib_checked;
+///
+/// assert_eq!(fib_checked(93), Some(12200160415121876738));
+/// assert_eq!(fib_checked(94), None);
+/// ```
+pub fn fib_checked(n: u32) -> Option<u64> {
+    fib_u128(n).and_then(|f| u64::try_from(f).ok())
+}
+
+/// Calculate F(n) mod m in O(log n) using fast doubling
+///
+/// # Returns
+/// `None` if the modulus is zero
+pub fn fib_mod(n: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    let m = m as u128;
+    let (mut a, mut b) = (0u128, 1u128 % m);
+
+    // Walk the bits of n from the most significant, keeping (F(k), F(k+1)) mod m
+    for bit in (0..u64::BITS - n.leading_zeros()).rev() {
+        let c = a * ((2 * b + m - a) % m) % m;
+        let d = (a * a % m + b * b % m) % m;
+        if (n >> bit) & 1 == 0 {
+            (a, b) = (c, d);
+        } else {
+            (a, b) = (d, (c + d) % m);
+        }
+    }
+    Some(a as u64)
+}
+
+/// Calculate F(n) as an arbitrary-precision integer using fast doubling
+#[cfg(feature = "bigint")]
+pub fn fib_big(n: u64) -> BigUint {
+    fib_big_pair(n).0
+}
+
+/// Calculate (F(n), F(n+1)) as arbitrary-precision integers
+#[cfg(feature = "bigint")]
+pub fn fib_big_pair(n: u64) -> (BigUint, BigUint) {
+    let (mut a, mut b) = (BigUint::zero(), BigUint::one());
+
+    for bit in (0..u64::BITS - n.leading_zeros()).rev() {
+        let c = &a * ((&b << 1) - &a);
+        let d = &a * &a + &b * &b;
+        if (n >> bit) & 1 == 0 {
+            (a, b) = (c, d);
+        } else {
+            b = &c + &d;
+            a = d;
+        }
+    }
+    (a, b)
+}
+
+/// Find the index of a Fibonacci number
+///
+/// # Returns
+/// The smallest n with F(n) = `value` (so 1 maps to 1), or `None` if
+/// `value` is not a Fibonacci number
+///
+/// # Examples
+/// ```
+/// use fibonacci_sequence::fibonacci::fibonacci_index;
+///
+/// assert_eq!(fibonacci_index(55), Some(10));
+/// assert_eq!(fibonacci_index(56), None);
+/// ```
+pub fn fibonacci_index(value: u64) -> Option<u32> {
+    let (mut a, mut b, mut index) = (0u64, 1u64, 0u32);
+    while a < value {
+        match a.checked_add(b) {
+            Some(next) => (a, b) = (b, next),
+            // b = F(93) is the last Fibonacci number below 2^64
+            None => return (b == value).then_some(index + 1),
+        }
+        index += 1;
+    }
+    (a == value).then_some(index)
+}
+
+/// Find the index of an arbitrary-precision Fibonacci number
+#[cfg(feature = "bigint")]
+pub fn fibonacci_index_big(value: &BigUint) -> Option<u64> {
+    let (mut a, mut b, mut index) = (BigUint::zero(), BigUint::one(), 0u64);
+    while &a < value {
+        let next = &a + &b;
+        (a, b) = (b, next);
+        index += 1;
+    }
+    (&a == value).then_some(index)
+}
+
+/// Calculate the Pisano period π(m), the period of F(n) mod m
+///
+/// Walks the sequence until the pair (0, 1) repeats, which takes at most 6m steps.
+///
+/// # Returns
+/// `None` if the modulus is zero
+///
+/// # Examples
+/// ```
+/// use fibonacci_sequence::fibonacci::pisano_period;
+///
+/// assert_eq!(pisano_period(10), Some(60));
+/// assert_eq!(pisano_period(0), None);
+/// ```
+pub fn pisano_period(m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    let m = m as u128;
+    let (mut a, mut b) = (0u128, 1 % m);
+    let mut period = 0;
+    loop {
+        (a, b) = (b, (a + b) % m);
+        period += 1;
+        if a == 0 && b == 1 % m {
+            return Some(period);
+        }
+    }
+}
+
+/// Calculate the Lucas number L(n), or `None` if it does not fit in a u64
+///
+/// Lucas numbers follow the Fibonacci recurrence from L(0) = 2, L(1) = 1,
+/// and L(n) = F(n-1) + F(n+1) = 2F(n+1) - F(n).
+///
+/// # Examples
+/// ```
+/// use fibonacci_sequence::fibonacci::lucas_checked;
+///
+/// assert_eq!(lucas_checked(0), Some(2));
+/// assert_eq!(lucas_checked(10), Some(123));
+/// ```
+pub fn lucas_checked(n: u32) -> Option<u64> {
+    let lucas = fib_u128(n.checked_add(1)?)?.checked_mul(2)? - fib_u128(n)?;
+    u64::try_from(lucas).ok()
+}
+
+/// Calculate the Lucas number L(n) as an arbitrary-precision integer
+#[cfg(feature = "bigint")]
+pub fn lucas_big(n: u64) -> BigUint {
+    let (f, f1) = fib_big_pair(n);
+    (f1 << 1u32) - f
+}
+
+/// A way of calculating F(n) that a frontend can be set to use
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[cfg_attr(
+    feature = "settings",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum Algorithm {
+    /// [`fib`], exponential time
+    Recursive,
+    /// [`fib_memoized`]
+    Memoized,
+    /// [`generate_sequence_iterative`]
+    #[default]
+    Iterative,
+    /// [`fib_checked`], O(log n)
+    FastDoubling,
+}
+
+#[cfg(feature = "alloc")]
+impl Algorithm {
+    pub const ALL: [Algorithm; 4] = [
+        Algorithm::Recursive,
+        Algorithm::Memoized,
+        Algorithm::Iterative,
+        Algorithm::FastDoubling,
+    ];
+
+    /// Name used in settings files and on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Recursive => "recursive",
+            Algorithm::Memoized => "memoized",
+            Algorithm::Iterative => "iterative",
+            Algorithm::FastDoubling => "fast-doubling",
+        }
+    }
+
+    /// Largest n worth asking for: F(93) is the last u64, and recursion
+    /// past F(40) takes seconds
+    pub fn max_n(self) -> u32 {
+        match self {
+            Algorithm::Recursive => 40,
+            _ => 93,
+        }
+    }
+
+    /// Calculate F(n), or `None` if n is above [`Algorithm::max_n`]
+    pub fn nth(self, n: u32) -> Option<u64> {
+        if n > self.max_n() {
+            return None;
+        }
+        match self {
+            Algorithm::Recursive => Some(fib(n)),
+            Algorithm::Memoized => Some(fib_memoized(n)),
+            Algorithm::Iterative => generate_sequence_iterative(n).pop(),
+            Algorithm::FastDoubling => fib_checked(n),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -107,18 +372,31 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "alloc")]
     fn test_generate_sequence() {
         let seq = generate_sequence(5);
         assert_eq!(seq, vec![0, 1, 1, 2, 3, 5]);
     }
 
     #[test]
+    #[cfg(feature = "alloc")]
     fn test_fib_memoized() {
         assert_eq!(fib_memoized(10), 55);
         assert_eq!(fib_memoized(20), 6765);
     }
 
     #[test]
+    #[cfg(feature = "alloc")]
+    fn test_algorithms_agree() {
+        for algorithm in Algorithm::ALL {
+            assert_eq!(algorithm.nth(30), Some(832040), "{}", algorithm.name());
+            assert_eq!(algorithm.nth(algorithm.max_n() + 1), None);
+        }
+        assert_eq!(Algorithm::Iterative.nth(93), fib_checked(93));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
     fn test_generate_sequence_iterative() {
         let seq = generate_sequence_iterative(5);
         assert_eq!(seq, vec![0, 1, 1, 2, 3, 5]);
@@ -135,5 +413,119 @@ mod tests {
         assert!(!is_fibonacci_number(4));
         assert!(!is_fibonacci_number(6));
         assert!(!is_fibonacci_number(7));
+
+        // Beyond 2^53 a float square root is no longer exact
+        assert!(is_fibonacci_number(12200160415121876738));
+        assert!(!is_fibonacci_number(12200160415121876739));
+        assert!(is_fibonacci_number(fib_checked(80).unwrap()));
+        assert!(!is_fibonacci_number(fib_checked(80).unwrap() - 1));
+        assert!(!is_fibonacci_number(u64::MAX));
+    }
+
+    #[test]
+    fn test_fib_u128() {
+        assert_eq!(fib_u128(0), Some(0));
+        assert_eq!(fib_u128(1), Some(1));
+        assert_eq!(fib_u128(100), Some(354224848179261915075));
+        assert_eq!(fib_u128(186), Some(332825110087067562321196029789634457848));
+        assert_eq!(fib_u128(187), None);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_fib_checked_matches_iterative() {
+        let seq = generate_sequence_iterative(93);
+        for (n, &expected) in seq.iter().enumerate() {
+            assert_eq!(fib_checked(n as u32), Some(expected));
+        }
+        assert_eq!(fib_checked(94), None);
+    }
+
+    #[test]
+    fn test_fib_mod() {
+        assert_eq!(fib_mod(10, 7), Some(55 % 7));
+        assert_eq!(fib_mod(93, u64::MAX), Some(12200160415121876738));
+        assert_eq!(fib_mod(100, 1_000_000_007), Some((354224848179261915075u128 % 1_000_000_007) as u64));
+        assert_eq!(fib_mod(1_000_000, 1), Some(0));
+        #[cfg(feature = "bigint")]
+        assert_eq!(
+            fib_mod(1000, u64::MAX - 58),
+            Some((fib_big(1000) % BigUint::from(u64::MAX - 58)).try_into().unwrap())
+        );
+        assert_eq!(fib_mod(5, 0), None);
+
+        // The Pisano period for 10 is 60
+        assert_eq!(fib_mod(1_000_000_000_000, 10), fib_mod(1_000_000_000_000 % 60, 10));
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn test_fib_big() {
+        assert_eq!(fib_big(0), BigUint::zero());
+        assert_eq!(fib_big(93), BigUint::from(12200160415121876738u64));
+        assert_eq!(
+            fib_big(200).to_string(),
+            "280571172992510140037611932413038677189525"
+        );
+        let (a, b) = fib_big_pair(300);
+        assert_eq!(&a + &b, fib_big(302));
+    }
+
+    #[test]
+    fn test_fibonacci_index() {
+        for n in 0..=93 {
+            let value = fib_checked(n).unwrap();
+            // F(1) = F(2) = 1 resolves to the smaller index
+            let expected = if n == 2 { 1 } else { n };
+            assert_eq!(fibonacci_index(value), Some(expected));
+        }
+        assert_eq!(fibonacci_index(4), None);
+        assert_eq!(fibonacci_index(12200160415121876737), None);
+        assert_eq!(fibonacci_index(u64::MAX), None);
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn test_fibonacci_index_big() {
+        assert_eq!(fibonacci_index_big(&fib_big(500)), Some(500));
+        assert_eq!(fibonacci_index_big(&(fib_big(500) + 1u32)), None);
+        assert_eq!(fibonacci_index_big(&BigUint::zero()), Some(0));
+    }
+
+    #[test]
+    fn test_pisano_period() {
+        // OEIS A001175
+        let expected = [1, 3, 8, 6, 20, 24, 16, 12, 24, 60, 10, 24, 28, 48, 40];
+        for (m, &period) in (1..).zip(expected.iter()) {
+            assert_eq!(pisano_period(m), Some(period), "m = {}", m);
+        }
+        assert_eq!(pisano_period(1000), Some(1500));
+        assert_eq!(pisano_period(0), None);
+
+        let m = 1_000_003;
+        let period = pisano_period(m).unwrap();
+        assert_eq!(fib_mod(period, m), Some(0));
+        assert_eq!(fib_mod(period + 1, m), Some(1));
+    }
+
+    #[test]
+    fn test_lucas() {
+        let expected = [2, 1, 3, 4, 7, 11, 18, 29, 47, 76, 123];
+        for (n, &l) in expected.iter().enumerate() {
+            assert_eq!(lucas_checked(n as u32), Some(l));
+        }
+        assert_eq!(lucas_checked(92), Some(16860207025497407047));
+        assert_eq!(lucas_checked(93), None);
+        assert_eq!(lucas_checked(u32::MAX), None);
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn test_lucas_big() {
+        for n in 0..=92 {
+            assert_eq!(lucas_big(n), BigUint::from(lucas_checked(n as u32).unwrap()));
+        }
+        // L(n) = F(2n) / F(n)
+        assert_eq!(lucas_big(300) * fib_big(300), fib_big(600));
     }
 }
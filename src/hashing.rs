@@ -0,0 +1,213 @@
+use core::hash::{BuildHasher, Hasher};
+
+/// 2^64 / φ rounded to the nearest odd integer (Knuth's multiplicative constant)
+pub const GOLDEN_RATIO_64: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Map a key to a table index using Fibonacci (golden-ratio multiplicative) hashing
+///
+/// # Arguments
+/// * `key` - The value to hash
+/// * `bits` - log2 of the table size, at most `usize::BITS` (64, or 32 on
+///   32-bit targets such as thumbv7em)
+///
+/// # Returns
+/// An index in the range `0..2^bits`
+///
+/// # Panics
+/// If `bits` is greater than `usize::BITS`, where the index would be truncated
+///
+/// # Examples
+/// ```
+/// use fibonacci_sequence::hashing::fib_hash;
+///
+/// assert!(fib_hash(42, 10) < 1024);
+/// assert_eq!(fib_hash(42, 0), 0);
+/// ```
+pub fn fib_hash(key: u64, bits: u32) -> usize {
+    assert!(bits <= usize::BITS, "fib_hash: bits must be at most {}, got {}", usize::BITS, bits);
+    match bits {
+        0 => 0,
+        _ => (key.wrapping_mul(GOLDEN_RATIO_64) >> (64 - bits)) as usize,
+    }
+}
+
+/// Hasher based on Fibonacci hashing
+///
+/// Very fast for integer keys; not resistant to HashDoS, so only use it
+/// for keys that are not attacker-controlled.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FibonacciHasher {
+    state: u64,
+}
+
+impl FibonacciHasher {
+    /// Fold one 64-bit word into the hasher state
+    fn add_word(&mut self, word: u64) {
+        self.state = (self.state.rotate_left(5) ^ word).wrapping_mul(GOLDEN_RATIO_64);
+    }
+}
+
+impl Hasher for FibonacciHasher {
+    fn finish(&self) -> u64 {
+        // The multiplication leaves its best bits at the top, while std's
+        // HashMap picks buckets from the low bits, so fold them down
+        self.state ^ (self.state >> 32)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(chunk);
+            self.add_word(u64::from_le_bytes(word));
+        }
+
+        let remainder = chunks.remainder();
+        if !remainder.is_empty() {
+            let mut word = [0u8; 8];
+            word[..remainder.len()].copy_from_slice(remainder);
+            self.add_word(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add_word(i as u64);
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.add_word(i as u64);
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add_word(i as u64);
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add_word(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add_word(i as u64);
+    }
+}
+
+/// `BuildHasher` for using `FibonacciHasher` with std collections
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use fibonacci_sequence::hashing::BuildFibonacciHasher;
+///
+/// let mut map: HashMap<u32, u64, BuildFibonacciHasher> = HashMap::default();
+/// map.insert(10, 55);
+/// assert_eq!(map.get(&10), Some(&55));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuildFibonacciHasher;
+
+impl BuildHasher for BuildFibonacciHasher {
+    type Hasher = FibonacciHasher;
+
+    fn build_hasher(&self) -> FibonacciHasher {
+        FibonacciHasher::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    #[should_panic(expected = "bits must be at most")]
+    fn test_fib_hash_rejects_wide_tables() {
+        // More bits than an index can hold would be silently truncated
+        fib_hash(1, usize::BITS + 1);
+    }
+
+    /// Chi-squared statistic of bucket counts against a uniform distribution
+    fn chi_squared(counts: &[usize], total: usize) -> f64 {
+        let expected = total as f64 / counts.len() as f64;
+        counts
+            .iter()
+            .map(|&c| (c as f64 - expected).powi(2) / expected)
+            .sum()
+    }
+
+    #[test]
+    fn test_fib_hash_range() {
+        for bits in 1..=16 {
+            for key in 0..1000u64 {
+                assert!(fib_hash(key, bits) < (1usize << bits));
+            }
+        }
+        assert_eq!(fib_hash(u64::MAX, 0), 0);
+        assert_eq!(fib_hash(1, 64), GOLDEN_RATIO_64 as usize);
+    }
+
+    #[test]
+    fn test_fib_hash_sequential_keys_spread_evenly() {
+        // Fibonacci hashing spreads consecutive keys almost perfectly
+        let bits = 10;
+        let buckets = 1usize << bits;
+        let mut counts = vec![0usize; buckets];
+        for key in 0..(buckets * 8) as u64 {
+            counts[fib_hash(key, bits)] += 1;
+        }
+
+        let max = *counts.iter().max().unwrap();
+        let min = *counts.iter().min().unwrap();
+        assert!(max - min <= 3, "bucket loads ranged from {} to {}", min, max);
+
+        // A random hash would land near 1023 here; golden-ratio spacing is far more even
+        let chi2 = chi_squared(&counts, buckets * 8);
+        assert!(chi2 < 300.0, "chi-squared too large: {}", chi2);
+    }
+
+    #[test]
+    fn test_fib_hash_strided_keys_distribution() {
+        // Keys that share their low bits would collide under `key % buckets`
+        let bits = 8;
+        let buckets = 1usize << bits;
+        let total = buckets * 32;
+        let mut counts = vec![0usize; buckets];
+        for i in 0..total as u64 {
+            counts[fib_hash(i * 1024, bits)] += 1;
+        }
+
+        // 99.9th percentile of chi-squared with 255 degrees of freedom is ~330
+        let chi2 = chi_squared(&counts, total);
+        assert!(chi2 < 330.0, "chi-squared too large: {}", chi2);
+    }
+
+    #[test]
+    fn test_hasher_low_bits_distribution() {
+        let buckets = 256;
+        let total = buckets * 32;
+        let mut counts = vec![0usize; buckets];
+        for key in 0..total as u32 {
+            let hash = BuildFibonacciHasher.hash_one(key * 64);
+            counts[(hash % buckets as u64) as usize] += 1;
+        }
+
+        let chi2 = chi_squared(&counts, total);
+        assert!(chi2 < 330.0, "chi-squared too large: {}", chi2);
+    }
+
+    #[test]
+    fn test_hasher_distinguishes_byte_strings() {
+        let words = ["", "a", "b", "ab", "ba", "fibonacci", "fibonaccj", "golden ratio"];
+        let hashes: HashSet<u64> = words.iter().map(|w| BuildFibonacciHasher.hash_one(w)).collect();
+        assert_eq!(hashes.len(), words.len());
+    }
+
+    #[test]
+    fn test_hashmap_with_fibonacci_hasher() {
+        let mut map: HashMap<u64, u64, BuildFibonacciHasher> = HashMap::default();
+        for i in 0..10_000u64 {
+            map.insert(i, i * i);
+        }
+        assert_eq!(map.len(), 10_000);
+        assert!((0..10_000u64).all(|i| map[&i] == i * i));
+    }
+}
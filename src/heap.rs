@@ -0,0 +1,744 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use core::mem;
+
+/// Handle to an entry in a `FibonacciHeap`, used for decrease-key and delete
+///
+/// Handles stay valid until their entry is popped or deleted; using a stale
+/// handle returns `HeapError::InvalidHandle` rather than touching another entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle {
+    index: usize,
+    generation: u32,
+}
+
+/// Errors returned by handle-based heap operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeapError {
+    /// The handle does not refer to an entry in this heap
+    InvalidHandle,
+    /// `decrease_key` was called with a key larger than the current one
+    KeyIncreased,
+}
+
+impl fmt::Display for HeapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeapError::InvalidHandle => write!(f, "handle does not refer to an entry in this heap"),
+            HeapError::KeyIncreased => write!(f, "new key is greater than the current key"),
+        }
+    }
+}
+
+impl core::error::Error for HeapError {}
+
+/// Translates handles of a heap that was merged into another one
+#[derive(Debug, Clone, Copy)]
+pub struct MergedHandles {
+    offset: usize,
+}
+
+impl MergedHandles {
+    /// Get the handle in the merged heap for a handle of the absorbed heap
+    pub fn translate(&self, handle: Handle) -> Handle {
+        Handle {
+            index: handle.index + self.offset,
+            generation: handle.generation,
+        }
+    }
+}
+
+/// A node in the heap; siblings form a circular doubly linked list
+struct Node<K, V> {
+    key: K,
+    value: V,
+    parent: Option<usize>,
+    child: Option<usize>,
+    left: usize,
+    right: usize,
+    degree: usize,
+    marked: bool,
+}
+
+enum Slot<K, V> {
+    Occupied { generation: u32, node: Node<K, V> },
+    Vacant { generation: u32, next_free: Option<usize> },
+}
+
+/// Min-priority queue backed by a Fibonacci heap
+///
+/// Amortized costs: `push`, `peek` and `decrease_key` are O(1), `pop` and
+/// `delete` are O(log n). `merge` links the root lists in O(1) but moves every
+/// slot of the absorbed heap, so it is O(len(other)).
+///
+/// # Examples
+/// ```
+/// use fibonacci_sequence::heap::FibonacciHeap;
+///
+/// let mut heap = FibonacciHeap::new();
+/// heap.push(5, "five");
+/// let three = heap.push(3, "three");
+/// heap.push(8, "eight");
+///
+/// heap.decrease_key(three, 1).unwrap();
+/// assert_eq!(heap.pop(), Some((1, "three")));
+/// assert_eq!(heap.pop(), Some((5, "five")));
+/// ```
+pub struct FibonacciHeap<K, V> {
+    slots: Vec<Slot<K, V>>,
+    free: Option<usize>,
+    min: Option<usize>,
+    len: usize,
+}
+
+impl<K: Ord, V> Default for FibonacciHeap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> FibonacciHeap<K, V> {
+    /// Create an empty heap
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: None,
+            min: None,
+            len: 0,
+        }
+    }
+
+    /// Number of entries in the heap
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the heap has no entries
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Insert a key/value pair, returning a handle to the new entry
+    pub fn push(&mut self, key: K, value: V) -> Handle {
+        let node = Node {
+            key,
+            value,
+            parent: None,
+            child: None,
+            left: 0,
+            right: 0,
+            degree: 0,
+            marked: false,
+        };
+        let handle = self.allocate(node);
+        let x = handle.index;
+        self.node_mut(x).left = x;
+        self.node_mut(x).right = x;
+        self.add_root(x);
+        self.len += 1;
+        handle
+    }
+
+    /// Get the entry with the smallest key
+    pub fn peek(&self) -> Option<(&K, &V)> {
+        self.min.map(|m| {
+            let node = self.node(m);
+            (&node.key, &node.value)
+        })
+    }
+
+    /// Remove and return the entry with the smallest key
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        let z = self.min?;
+
+        // Promote all children of the minimum to roots
+        if let Some(child) = self.node(z).child {
+            for c in self.siblings(child) {
+                self.unlink(c);
+                let node = self.node_mut(c);
+                node.parent = None;
+                node.marked = false;
+                self.splice(c, z);
+            }
+            self.node_mut(z).child = None;
+        }
+
+        let right = self.node(z).right;
+        self.unlink(z);
+        if right == z {
+            self.min = None;
+        } else {
+            self.min = Some(right);
+            self.consolidate();
+        }
+
+        self.len -= 1;
+        let node = self.release(z);
+        Some((node.key, node.value))
+    }
+
+    /// Get the entry for a handle, if it is still in the heap
+    pub fn get(&self, handle: Handle) -> Option<(&K, &V)> {
+        let x = self.resolve(handle).ok()?;
+        let node = self.node(x);
+        Some((&node.key, &node.value))
+    }
+
+    /// Check if a handle still refers to an entry in the heap
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.resolve(handle).is_ok()
+    }
+
+    /// Lower the key of an entry
+    ///
+    /// Returns `HeapError::KeyIncreased` if `new_key` is greater than the current key.
+    pub fn decrease_key(&mut self, handle: Handle, new_key: K) -> Result<(), HeapError> {
+        let x = self.resolve(handle)?;
+        if new_key > self.node(x).key {
+            return Err(HeapError::KeyIncreased);
+        }
+        self.node_mut(x).key = new_key;
+
+        if let Some(p) = self.node(x).parent
+            && self.node(x).key < self.node(p).key
+        {
+            self.cut(x, p);
+            self.cascading_cut(p);
+        }
+
+        if let Some(m) = self.min
+            && self.node(x).key < self.node(m).key
+        {
+            self.min = Some(x);
+        }
+        Ok(())
+    }
+
+    /// Remove an arbitrary entry from the heap
+    pub fn delete(&mut self, handle: Handle) -> Result<(K, V), HeapError> {
+        let x = self.resolve(handle)?;
+        if let Some(p) = self.node(x).parent {
+            self.cut(x, p);
+            self.cascading_cut(p);
+        }
+
+        // Treat the entry as if its key were -infinity and extract it
+        self.min = Some(x);
+        Ok(self.pop().expect("heap contains the deleted entry"))
+    }
+
+    /// Move all entries of `other` into this heap, in O(len(other))
+    ///
+    /// Handles from `other` can be converted with the returned `MergedHandles`.
+    pub fn merge(&mut self, other: FibonacciHeap<K, V>) -> MergedHandles {
+        let offset = self.slots.len();
+
+        let mut other_free_tail = None;
+        for (i, mut slot) in other.slots.into_iter().enumerate() {
+            match &mut slot {
+                Slot::Occupied { node, .. } => {
+                    node.parent = node.parent.map(|p| p + offset);
+                    node.child = node.child.map(|c| c + offset);
+                    node.left += offset;
+                    node.right += offset;
+                }
+                Slot::Vacant { next_free, .. } => match next_free {
+                    Some(next) => *next += offset,
+                    None => other_free_tail = Some(i + offset),
+                },
+            }
+            self.slots.push(slot);
+        }
+
+        // Chain the absorbed free list in front of ours
+        if let Some(tail) = other_free_tail {
+            if let Slot::Vacant { next_free, .. } = &mut self.slots[tail] {
+                *next_free = self.free;
+            }
+            self.free = other.free.map(|f| f + offset);
+        }
+
+        if let Some(other_min) = other.min.map(|m| m + offset) {
+            match self.min {
+                None => self.min = Some(other_min),
+                Some(m) => {
+                    // Concatenate the two circular root lists
+                    let m_right = self.node(m).right;
+                    let o_left = self.node(other_min).left;
+                    self.node_mut(m).right = other_min;
+                    self.node_mut(other_min).left = m;
+                    self.node_mut(o_left).right = m_right;
+                    self.node_mut(m_right).left = o_left;
+
+                    if self.node(other_min).key < self.node(m).key {
+                        self.min = Some(other_min);
+                    }
+                }
+            }
+        }
+
+        self.len += other.len;
+        MergedHandles { offset }
+    }
+
+    /// Remove all entries, invalidating every handle
+    ///
+    /// The slots are kept, so handles taken before the call stay stale even
+    /// once new entries reuse them.
+    pub fn clear(&mut self) {
+        let mut next_free = None;
+        for (index, slot) in self.slots.iter_mut().enumerate().rev() {
+            let generation = match slot {
+                Slot::Occupied { generation, .. } => generation.wrapping_add(1),
+                Slot::Vacant { generation, .. } => *generation,
+            };
+            *slot = Slot::Vacant { generation, next_free };
+            next_free = Some(index);
+        }
+        self.free = next_free;
+        self.min = None;
+        self.len = 0;
+    }
+
+    /// Resolve a handle to a slot index, checking that it is still live
+    fn resolve(&self, handle: Handle) -> Result<usize, HeapError> {
+        match self.slots.get(handle.index) {
+            Some(Slot::Occupied { generation, .. }) if *generation == handle.generation => {
+                Ok(handle.index)
+            }
+            _ => Err(HeapError::InvalidHandle),
+        }
+    }
+
+    fn allocate(&mut self, node: Node<K, V>) -> Handle {
+        match self.free {
+            Some(index) => {
+                let (generation, next_free) = match self.slots[index] {
+                    Slot::Vacant { generation, next_free } => (generation, next_free),
+                    Slot::Occupied { .. } => unreachable!("free list points at an occupied slot"),
+                };
+                self.free = next_free;
+                self.slots[index] = Slot::Occupied { generation, node };
+                Handle { index, generation }
+            }
+            None => {
+                self.slots.push(Slot::Occupied { generation: 0, node });
+                Handle {
+                    index: self.slots.len() - 1,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    fn release(&mut self, index: usize) -> Node<K, V> {
+        let generation = match self.slots[index] {
+            Slot::Occupied { generation, .. } => generation.wrapping_add(1),
+            Slot::Vacant { .. } => unreachable!("released a vacant slot"),
+        };
+        let vacant = Slot::Vacant {
+            generation,
+            next_free: self.free,
+        };
+        self.free = Some(index);
+        match mem::replace(&mut self.slots[index], vacant) {
+            Slot::Occupied { node, .. } => node,
+            Slot::Vacant { .. } => unreachable!(),
+        }
+    }
+
+    fn node(&self, index: usize) -> &Node<K, V> {
+        match &self.slots[index] {
+            Slot::Occupied { node, .. } => node,
+            Slot::Vacant { .. } => unreachable!("heap links point at a vacant slot"),
+        }
+    }
+
+    fn node_mut(&mut self, index: usize) -> &mut Node<K, V> {
+        match &mut self.slots[index] {
+            Slot::Occupied { node, .. } => node,
+            Slot::Vacant { .. } => unreachable!("heap links point at a vacant slot"),
+        }
+    }
+
+    /// Collect the circular sibling list starting at `start`
+    fn siblings(&self, start: usize) -> Vec<usize> {
+        let mut result = vec![start];
+        let mut current = self.node(start).right;
+        while current != start {
+            result.push(current);
+            current = self.node(current).right;
+        }
+        result
+    }
+
+    /// Insert `x` into the sibling list right after `anchor`
+    fn splice(&mut self, x: usize, anchor: usize) {
+        let anchor_right = self.node(anchor).right;
+        self.node_mut(x).left = anchor;
+        self.node_mut(x).right = anchor_right;
+        self.node_mut(anchor_right).left = x;
+        self.node_mut(anchor).right = x;
+    }
+
+    /// Remove `x` from its sibling list, leaving it as a singleton
+    fn unlink(&mut self, x: usize) {
+        let left = self.node(x).left;
+        let right = self.node(x).right;
+        self.node_mut(left).right = right;
+        self.node_mut(right).left = left;
+        self.node_mut(x).left = x;
+        self.node_mut(x).right = x;
+    }
+
+    /// Add a singleton node to the root list, updating the minimum
+    fn add_root(&mut self, x: usize) {
+        self.node_mut(x).parent = None;
+        match self.min {
+            None => self.min = Some(x),
+            Some(m) => {
+                self.splice(x, m);
+                if self.node(x).key < self.node(m).key {
+                    self.min = Some(x);
+                }
+            }
+        }
+    }
+
+    /// Make root `y` a child of root `x`
+    fn link(&mut self, y: usize, x: usize) {
+        self.unlink(y);
+        self.node_mut(y).parent = Some(x);
+        self.node_mut(y).marked = false;
+        match self.node(x).child {
+            Some(c) => self.splice(y, c),
+            None => self.node_mut(x).child = Some(y),
+        }
+        self.node_mut(x).degree += 1;
+    }
+
+    /// Merge roots of equal degree until all root degrees are distinct
+    fn consolidate(&mut self) {
+        let Some(start) = self.min else { return };
+
+        let mut by_degree: Vec<Option<usize>> = Vec::new();
+        for root in self.siblings(start) {
+            let mut x = root;
+            let mut degree = self.node(x).degree;
+            loop {
+                if degree >= by_degree.len() {
+                    by_degree.resize(degree + 1, None);
+                }
+                match by_degree[degree].take() {
+                    Some(mut y) => {
+                        if self.node(y).key < self.node(x).key {
+                            mem::swap(&mut x, &mut y);
+                        }
+                        self.link(y, x);
+                        degree += 1;
+                    }
+                    None => {
+                        by_degree[degree] = Some(x);
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.min = None;
+        for x in by_degree.into_iter().flatten() {
+            match self.min {
+                Some(m) if self.node(m).key <= self.node(x).key => {}
+                _ => self.min = Some(x),
+            }
+        }
+    }
+
+    /// Move `x` from the children of `parent` to the root list
+    fn cut(&mut self, x: usize, parent: usize) {
+        if self.node(parent).child == Some(x) {
+            let right = self.node(x).right;
+            self.node_mut(parent).child = if right == x { None } else { Some(right) };
+        }
+        self.unlink(x);
+        self.node_mut(parent).degree -= 1;
+        self.node_mut(x).marked = false;
+        self.add_root(x);
+    }
+
+    /// Cut marked ancestors so that no node loses more than one child
+    fn cascading_cut(&mut self, mut y: usize) {
+        while let Some(parent) = self.node(y).parent {
+            if !self.node(y).marked {
+                self.node_mut(y).marked = true;
+                return;
+            }
+            self.cut(y, parent);
+            y = parent;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fibonacci;
+
+    /// Small deterministic generator so the tests don't need a rand dependency
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self) -> u64 {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            self.0 >> 33
+        }
+    }
+
+    /// Size of the subtree rooted at `x`, checking heap order along the way
+    fn subtree_size<K: Ord, V>(heap: &FibonacciHeap<K, V>, x: usize) -> usize {
+        let node = heap.node(x);
+        let mut size = 1;
+        if let Some(child) = node.child {
+            let children = heap.siblings(child);
+            assert_eq!(children.len(), node.degree, "degree does not match child count");
+            for c in children {
+                assert_eq!(heap.node(c).parent, Some(x));
+                assert!(heap.node(c).key >= node.key, "heap order violated");
+                size += subtree_size(heap, c);
+            }
+        }
+        size
+    }
+
+    /// Check that every node of degree k has at least F(k + 2) descendants (itself included)
+    fn assert_degree_bound<K: Ord, V>(heap: &FibonacciHeap<K, V>) {
+        let Some(min) = heap.min else {
+            assert_eq!(heap.len(), 0);
+            return;
+        };
+
+        let mut total = 0;
+        let mut stack = heap.siblings(min);
+        for &root in &stack {
+            total += subtree_size(heap, root);
+        }
+        assert_eq!(total, heap.len());
+
+        while let Some(x) = stack.pop() {
+            let node = heap.node(x);
+            let size = subtree_size(heap, x) as u64;
+            let bound = fibonacci::fib(node.degree as u32 + 2);
+            assert!(
+                size >= bound,
+                "node of degree {} has {} descendants, expected at least F({}) = {}",
+                node.degree,
+                size,
+                node.degree + 2,
+                bound
+            );
+            if let Some(child) = node.child {
+                stack.extend(heap.siblings(child));
+            }
+        }
+    }
+
+    #[test]
+    fn test_push_pop_sorted() {
+        let mut heap = FibonacciHeap::new();
+        let mut rng = Lcg(7);
+        let mut keys: Vec<u64> = (0..500).map(|_| rng.next() % 1000).collect();
+        for &k in &keys {
+            heap.push(k, ());
+        }
+        assert_eq!(heap.len(), 500);
+
+        keys.sort();
+        let popped: Vec<u64> = std::iter::from_fn(|| heap.pop().map(|(k, _)| k)).collect();
+        assert_eq!(popped, keys);
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut heap = FibonacciHeap::new();
+        assert_eq!(heap.peek(), None);
+        heap.push(3, 'a');
+        heap.push(1, 'b');
+        heap.push(2, 'c');
+        assert_eq!(heap.peek(), Some((&1, &'b')));
+        assert_eq!(heap.len(), 3);
+    }
+
+    #[test]
+    fn test_decrease_key() {
+        let mut heap = FibonacciHeap::new();
+        let handles: Vec<Handle> = (0..50).map(|i| heap.push(100 + i, i)).collect();
+        heap.pop();
+        heap.decrease_key(handles[40], 5).unwrap();
+        heap.decrease_key(handles[20], 7).unwrap();
+
+        assert_eq!(heap.pop(), Some((5, 40)));
+        assert_eq!(heap.pop(), Some((7, 20)));
+        assert_eq!(heap.pop(), Some((101, 1)));
+        assert_eq!(heap.decrease_key(handles[30], 200), Err(HeapError::KeyIncreased));
+    }
+
+    #[test]
+    fn test_delete() {
+        let mut heap = FibonacciHeap::new();
+        let handles: Vec<Handle> = (0..20).map(|i| heap.push(i, i * 10)).collect();
+        heap.pop();
+
+        assert_eq!(heap.delete(handles[10]), Ok((10, 100)));
+        assert_eq!(heap.delete(handles[10]), Err(HeapError::InvalidHandle));
+        assert!(!heap.contains(handles[10]));
+        assert_eq!(heap.len(), 18);
+
+        let popped: Vec<u32> = std::iter::from_fn(|| heap.pop().map(|(k, _)| k)).collect();
+        assert!(!popped.contains(&10));
+        assert_eq!(popped.len(), 18);
+    }
+
+    #[test]
+    fn test_stale_handle_after_slot_reuse() {
+        let mut heap = FibonacciHeap::new();
+        let first = heap.push(1, "first");
+        heap.pop();
+        let second = heap.push(2, "second");
+
+        assert_eq!(heap.get(first), None);
+        assert_eq!(heap.get(second), Some((&2, &"second")));
+        assert_eq!(heap.decrease_key(first, 0), Err(HeapError::InvalidHandle));
+    }
+
+    #[test]
+    fn test_stale_handle_after_clear() {
+        let mut heap = FibonacciHeap::new();
+        let old = heap.push(1, "old");
+        heap.push(2, "other");
+        heap.clear();
+        assert!(heap.is_empty());
+
+        let new = heap.push(3, "new");
+        assert_eq!(heap.get(old), None);
+        assert_eq!(heap.decrease_key(old, 0), Err(HeapError::InvalidHandle));
+        assert_eq!(heap.delete(old), Err(HeapError::InvalidHandle));
+        assert_eq!(heap.get(new), Some((&3, &"new")));
+
+        // Both old slots are reused before the heap grows
+        heap.push(4, "reused");
+        assert_eq!(heap.slots.len(), 2);
+        assert_eq!(heap.pop(), Some((3, "new")));
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut a = FibonacciHeap::new();
+        let mut b = FibonacciHeap::new();
+        for i in 0..10 {
+            a.push(i * 2, "a");
+        }
+        let b_handles: Vec<Handle> = (0..10).map(|i| b.push(i * 2 + 1, "b")).collect();
+        b.pop();
+        a.pop();
+
+        let translated = a.merge(b);
+        assert_eq!(a.len(), 18);
+
+        let h = translated.translate(b_handles[9]);
+        assert_eq!(a.get(h), Some((&19, &"b")));
+        a.decrease_key(h, 0).unwrap();
+        assert_eq!(a.pop(), Some((0, "b")));
+        assert_degree_bound(&a);
+
+        // Slots freed in either heap are reused after the merge
+        let reused = a.push(100, "new");
+        assert!(a.contains(reused));
+        assert_eq!(a.len(), 18);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // Directed graph as adjacency lists of (target, weight)
+        let graph: Vec<Vec<(usize, u64)>> = vec![
+            vec![(1, 7), (2, 9), (5, 14)],
+            vec![(0, 7), (2, 10), (3, 15)],
+            vec![(0, 9), (1, 10), (3, 11), (5, 2)],
+            vec![(1, 15), (2, 11), (4, 6)],
+            vec![(3, 6), (5, 9)],
+            vec![(0, 14), (2, 2), (4, 9)],
+        ];
+
+        let mut dist = vec![u64::MAX; graph.len()];
+        let mut handles = vec![None; graph.len()];
+        let mut heap = FibonacciHeap::new();
+        dist[0] = 0;
+        for (v, &d) in dist.iter().enumerate() {
+            handles[v] = Some(heap.push(d, v));
+        }
+
+        while let Some((d, u)) = heap.pop() {
+            handles[u] = None;
+            for &(v, w) in &graph[u] {
+                let candidate = d.saturating_add(w);
+                if candidate < dist[v] {
+                    dist[v] = candidate;
+                    heap.decrease_key(handles[v].unwrap(), candidate).unwrap();
+                }
+            }
+        }
+
+        assert_eq!(dist, vec![0, 7, 9, 20, 20, 11]);
+    }
+
+    #[test]
+    fn test_degree_bound_matches_fibonacci_numbers() {
+        let mut heap = FibonacciHeap::new();
+        let mut rng = Lcg(42);
+        let mut live: Vec<Handle> = Vec::new();
+
+        for round in 0..3000 {
+            match rng.next() % 10 {
+                0..=4 => live.push(heap.push(rng.next() % 10_000, round)),
+                5 | 6 => {
+                    if let Some((_, value)) = heap.pop() {
+                        live.retain(|&h| heap.contains(h));
+                        assert!(value < round);
+                    }
+                }
+                7 | 8 => {
+                    if !live.is_empty() {
+                        let h = live[(rng.next() as usize) % live.len()];
+                        let (&key, _) = heap.get(h).unwrap();
+                        heap.decrease_key(h, key.saturating_sub(rng.next() % 5000)).unwrap();
+                    }
+                }
+                _ => {
+                    if !live.is_empty() {
+                        let h = live.swap_remove((rng.next() as usize) % live.len());
+                        heap.delete(h).unwrap();
+                    }
+                }
+            }
+
+            if round % 100 == 0 {
+                assert_degree_bound(&heap);
+            }
+        }
+        assert_degree_bound(&heap);
+
+        // The degree bound implies max degree <= log_φ(n)
+        let phi = (1.0 + 5.0_f64.sqrt()) / 2.0;
+        let max_degree = heap
+            .slots
+            .iter()
+            .filter_map(|slot| match slot {
+                Slot::Occupied { node, .. } => Some(node.degree),
+                Slot::Vacant { .. } => None,
+            })
+            .max()
+            .unwrap_or(0);
+        assert!(max_degree as f64 <= (heap.len().max(1) as f64).ln() / phi.ln());
+    }
+}
@@ -0,0 +1,243 @@
+//! Past calculations listed in the egui app's history panel.
+//!
+//! Each finished calculation is recorded once: running the same n with the
+//! same algorithm again moves its entry to the top. Pinned entries are never
+//! dropped; unpinned ones beyond [`MAX_HISTORY`] are, oldest first.
+//!
+//! Times are whole seconds since the Unix epoch and are shown in UTC, like
+//! the log viewer.
+
+use std::path::Path;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(target_arch = "wasm32")]
+use web_time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::export::{self, ExportFormat};
+use crate::fibonacci::Algorithm;
+
+/// Most unpinned entries kept
+pub const MAX_HISTORY: usize = 50;
+
+/// A calculation the user ran
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub n: u32,
+    pub algorithm: Algorithm,
+    /// F(n)
+    pub value: u64,
+    /// When it finished, in seconds since the Unix epoch; 0 if unknown
+    #[serde(default)]
+    pub time: u64,
+    /// Kept regardless of [`MAX_HISTORY`]
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+impl HistoryEntry {
+    /// An unpinned entry finished now
+    pub fn new(n: u32, algorithm: Algorithm, value: u64) -> Self {
+        let time = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+        Self { n, algorithm, value, time, pinned: false }
+    }
+
+    /// `F(n) = value`
+    pub fn summary(&self) -> String {
+        format!("F({}) = {}", self.n, self.value)
+    }
+
+    /// `YYYY-MM-DD HH:MM` in UTC, or `earlier` if the time is unknown
+    pub fn time_label(&self) -> String {
+        match self.time {
+            0 => "earlier".to_string(),
+            time => utc(time)[..16].replace('T', " "),
+        }
+    }
+}
+
+/// One entry as exported
+#[derive(Debug, Serialize)]
+struct HistoryRow {
+    /// RFC 3339 UTC time, empty if unknown
+    time: String,
+    n: u32,
+    algorithm: &'static str,
+    value: u64,
+    pinned: bool,
+}
+
+/// The recorded calculations, oldest first
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct History {
+    entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// All entries, oldest first
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if nothing has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Record a calculation as the newest entry
+    ///
+    /// An earlier entry for the same n and algorithm is replaced, keeping
+    /// its pin.
+    pub fn record(&mut self, mut entry: HistoryEntry) {
+        if let Some(index) = self
+            .entries
+            .iter()
+            .position(|old| old.n == entry.n && old.algorithm == entry.algorithm)
+        {
+            entry.pinned |= self.entries.remove(index).pinned;
+        }
+        self.entries.push(entry);
+
+        let unpinned = self.entries.iter().filter(|entry| !entry.pinned).count();
+        let mut excess = unpinned.saturating_sub(MAX_HISTORY);
+        self.entries.retain(|entry| {
+            let drop = excess > 0 && !entry.pinned;
+            excess -= usize::from(drop);
+            !drop
+        });
+    }
+
+    /// Pin or unpin the entry at `index`
+    pub fn toggle_pin(&mut self, index: usize) {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.pinned = !entry.pinned;
+        }
+    }
+
+    /// Delete the entry at `index`
+    pub fn remove(&mut self, index: usize) -> Option<HistoryEntry> {
+        (index < self.entries.len()).then(|| self.entries.remove(index))
+    }
+
+    /// Delete every entry that is not pinned
+    pub fn clear_unpinned(&mut self) {
+        self.entries.retain(|entry| entry.pinned);
+    }
+
+    /// Serialize the entries, oldest first
+    pub fn export(&self, format: ExportFormat) -> Result<String, String> {
+        let rows: Vec<HistoryRow> = self
+            .entries
+            .iter()
+            .map(|entry| HistoryRow {
+                time: if entry.time == 0 { String::new() } else { utc(entry.time) },
+                n: entry.n,
+                algorithm: entry.algorithm.name(),
+                value: entry.value,
+                pinned: entry.pinned,
+            })
+            .collect();
+        export::serialize(&rows, format)
+    }
+
+    /// Write the entries to a file, choosing the format from its extension
+    pub fn export_to_file(&self, path: &Path) -> Result<(), String> {
+        let text = self.export(export::format_for_path(path)?)?;
+        export::write_text(path, &text)
+    }
+}
+
+/// `YYYY-MM-DDTHH:MM:SSZ` for seconds since the Unix epoch
+fn utc(seconds: u64) -> String {
+    let (days, seconds) = (seconds / 86_400, seconds % 86_400);
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(n: u32, time: u64) -> HistoryEntry {
+        HistoryEntry { n, algorithm: Algorithm::Iterative, value: crate::fibonacci::fib_checked(n).unwrap(), time, pinned: false }
+    }
+
+    #[test]
+    fn test_record_replaces_and_bounds() {
+        let mut history = History::default();
+        history.record(entry(5, 1));
+        history.record(entry(8, 2));
+        history.toggle_pin(0);
+        history.record(entry(5, 3));
+        assert_eq!(history.entries().iter().map(|entry| entry.n).collect::<Vec<_>>(), [8, 5]);
+        assert!(history.entries()[1].pinned);
+        assert_eq!(history.entries()[1].time, 3);
+
+        for n in 10..10 + MAX_HISTORY as u32 + 2 {
+            history.record(entry(n % 94, 4));
+        }
+        assert_eq!(history.len(), MAX_HISTORY + 1);
+        // The pinned entry outlives older unpinned ones
+        assert_eq!(history.entries()[0].n, 5);
+        assert_eq!(history.entries()[1].n, 12);
+
+        assert_eq!(history.remove(0).map(|entry| entry.n), Some(5));
+        assert_eq!(history.remove(100), None);
+        history.toggle_pin(0);
+        history.clear_unpinned();
+        assert_eq!(history.len(), 1);
+    }
+
+    #[test]
+    fn test_labels() {
+        assert_eq!(utc(0), "1970-01-01T00:00:00Z");
+        assert_eq!(utc(951_782_400 + 3_723), "2000-02-29T01:02:03Z");
+        assert_eq!(utc(1_792_281_600), "2026-10-18T00:00:00Z");
+
+        let entry = entry(12, 1_792_281_600 + 60);
+        assert_eq!(entry.summary(), "F(12) = 144");
+        assert_eq!(entry.time_label(), "2026-10-18 00:01");
+        assert_eq!(HistoryEntry { time: 0, ..entry }.time_label(), "earlier");
+    }
+
+    #[test]
+    fn test_export() {
+        let mut history = History::default();
+        history.record(HistoryEntry { algorithm: Algorithm::FastDoubling, ..entry(10, 60) });
+        history.record(entry(3, 0));
+        history.toggle_pin(1);
+
+        let csv = history.export(ExportFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines, ["time,n,algorithm,value,pinned", "1970-01-01T00:01:00Z,10,fast-doubling,55,false", ",3,iterative,2,true"]);
+
+        let json: serde_json::Value = serde_json::from_str(&history.export(ExportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json[0]["algorithm"], "fast-doubling");
+        assert_eq!(json[1]["pinned"], true);
+    }
+}
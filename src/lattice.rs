@@ -0,0 +1,178 @@
+use crate::fibonacci;
+
+pub use crate::fibonacci::PHI;
+
+/// The golden angle in radians, π(3 − √5) ≈ 137.5°
+pub const GOLDEN_ANGLE: f64 = 2.399_963_229_728_653;
+
+/// Most points [`fibonacci_lattice`] generates, reached at F(30) = 832,040
+pub const MAX_LATTICE_POINTS: u64 = 1_000_000;
+
+/// Fractional part of a non-negative number
+fn fract(x: f64) -> f64 {
+    x - x.floor()
+}
+
+/// Generate the Fibonacci lattice with F(k) points on the unit square
+///
+/// Point i is `(i / F(k), frac(i * F(k-1) / F(k)))`, which gives one of the
+/// best-known low-discrepancy point sets of that size.
+///
+/// # Arguments
+/// * `k` - Index of the Fibonacci number giving the point count
+///
+/// # Returns
+/// F(k) points in `[0, 1)²`, or `None` if F(k) is above [`MAX_LATTICE_POINTS`] (k > 30)
+pub fn fibonacci_lattice(k: u32) -> Option<Vec<[f64; 2]>> {
+    let n = fibonacci::fib_checked(k).filter(|&n| n <= MAX_LATTICE_POINTS)?;
+    let step = fibonacci::fib_checked(k.saturating_sub(1))?;
+
+    let points = (0..n)
+        .map(|i| {
+            // Exact integer arithmetic keeps every point on the lattice
+            let y = (i as u128 * step as u128 % n as u128) as f64 / n as f64;
+            [i as f64 / n as f64, y]
+        })
+        .collect();
+    Some(points)
+}
+
+/// Generate a golden-ratio lattice with an arbitrary number of points on the unit square
+///
+/// This is the Fibonacci lattice generalized to any `n` by replacing
+/// F(k-1)/F(k) with its limit 1/φ.
+pub fn golden_lattice(n: usize) -> Vec<[f64; 2]> {
+    (0..n)
+        .map(|i| [(i as f64 + 0.5) / n as f64, fract(i as f64 / PHI)])
+        .collect()
+}
+
+/// Generate `n` points on the unit disk with Vogel's sunflower model
+///
+/// Point i sits at radius `sqrt((i + 0.5) / n)` and angle `i * GOLDEN_ANGLE`,
+/// so every annulus of equal area holds the same number of points.
+pub fn vogel_disk(n: usize) -> Vec<[f64; 2]> {
+    (0..n)
+        .map(|i| {
+            let r = ((i as f64 + 0.5) / n as f64).sqrt();
+            let theta = i as f64 * GOLDEN_ANGLE;
+            [r * theta.cos(), r * theta.sin()]
+        })
+        .collect()
+}
+
+/// Generate `n` quasi-uniform points on the unit sphere S²
+///
+/// Points are spaced evenly in z (so each covers the same area) and rotated
+/// by the golden angle around the z axis.
+pub fn fibonacci_sphere(n: usize) -> Vec<[f64; 3]> {
+    (0..n)
+        .map(|i| {
+            let z = 1.0 - (2.0 * i as f64 + 1.0) / n as f64;
+            let r = (1.0 - z * z).max(0.0).sqrt();
+            let theta = i as f64 * GOLDEN_ANGLE;
+            [r * theta.cos(), r * theta.sin(), z]
+        })
+        .collect()
+}
+
+/// Smallest distance between any two points (O(n²), intended for small sets)
+pub fn min_distance<const D: usize>(points: &[[f64; D]]) -> f64 {
+    let mut best = f64::INFINITY;
+    for (i, a) in points.iter().enumerate() {
+        for b in &points[i + 1..] {
+            let d2: f64 = a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum();
+            best = best.min(d2);
+        }
+    }
+    best.sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_constants() {
+        assert!((PHI - (1.0 + 5.0_f64.sqrt()) / 2.0).abs() < 1e-15);
+        assert!((GOLDEN_ANGLE - PI * (3.0 - 5.0_f64.sqrt())).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_fibonacci_lattice() {
+        let points = fibonacci_lattice(10).unwrap();
+        assert_eq!(points.len(), 55);
+        assert_eq!(points[0], [0.0, 0.0]);
+        assert_eq!(points[1], [1.0 / 55.0, 34.0 / 55.0]);
+        assert!(points.iter().all(|p| p.iter().all(|&c| (0.0..1.0).contains(&c))));
+
+        // Columns are i / 55, and every row of the 55x55 grid holds exactly one point
+        let mut rows: Vec<u64> = points.iter().map(|p| (p[1] * 55.0).round() as u64).collect();
+        rows.sort();
+        assert_eq!(rows, (0..55).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_fibonacci_lattice_spacing() {
+        // Nearest neighbours are about 0.94 / sqrt(n) apart, close to a hexagonal packing
+        let points = fibonacci_lattice(12).unwrap();
+        let n = points.len() as f64;
+        assert!(min_distance(&points) > 0.9 / n.sqrt());
+    }
+
+    #[test]
+    fn test_golden_lattice() {
+        let points = golden_lattice(100);
+        assert_eq!(points.len(), 100);
+        assert!(points.iter().all(|p| p.iter().all(|&c| (0.0..1.0).contains(&c))));
+        assert!(min_distance(&points) > 0.5 / 10.0);
+    }
+
+    #[test]
+    fn test_vogel_disk() {
+        let n = 500;
+        let points = vogel_disk(n);
+        assert_eq!(points.len(), n);
+        assert!(points.iter().all(|p| p[0].hypot(p[1]) <= 1.0));
+
+        // Equal-area property: the inner half of the area holds half the points
+        let inner = points.iter().filter(|p| p[0].hypot(p[1]) < 0.5_f64.sqrt()).count();
+        assert_eq!(inner, n / 2);
+    }
+
+    #[test]
+    fn test_fibonacci_sphere() {
+        let n = 1000;
+        let points = fibonacci_sphere(n);
+        assert_eq!(points.len(), n);
+
+        for p in &points {
+            let norm = (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt();
+            assert!((norm - 1.0).abs() < 1e-12);
+        }
+
+        // Quasi-uniform points are balanced around the origin
+        for axis in 0..3 {
+            let mean: f64 = points.iter().map(|p| p[axis]).sum::<f64>() / n as f64;
+            assert!(mean.abs() < 0.01, "axis {} mean {}", axis, mean);
+        }
+
+        // Spacing is close to the ideal sqrt(4π / n)
+        let ideal = (4.0 * PI / n as f64).sqrt();
+        assert!(min_distance(&points) > 0.5 * ideal);
+    }
+
+    #[test]
+    fn test_empty_sets() {
+        assert!(golden_lattice(0).is_empty());
+        assert!(vogel_disk(0).is_empty());
+        assert!(fibonacci_sphere(0).is_empty());
+        assert_eq!(fibonacci_lattice(0), Some(Vec::new()));
+        assert_eq!(fibonacci_lattice(1), Some(vec![[0.0, 0.0]]));
+        // F(31) is above the point cap, F(94) overflows a u64
+        assert_eq!(fibonacci_lattice(30).map(|points| points.len()), Some(832_040));
+        assert_eq!(fibonacci_lattice(31), None);
+        assert_eq!(fibonacci_lattice(94), None);
+    }
+}
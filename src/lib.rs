@@ -0,0 +1,73 @@
+//! Fibonacci sequence algorithms, number representations and visualization.
+//!
+//! The `fibonacci_sequence` binary is a thin egui frontend on top of this crate;
+//! other crates can depend on it to reuse the math, the spiral geometry and the
+//! input validation.
+//!
+//! # Features
+//! * `egui-app` (default) - the egui frontend, [`app`] and [`ui`], with its
+//!   [`history`] and saved [`session`]
+//! * `gtk-app` - the GTK 4 frontends
+//! * `cli` (default) - the `fib` command-line tool, [`cli`]
+//! * `bigint` - arbitrary-precision results, [`phinary`] and [`radix`]
+//! * `export` - CSV, JSON and NDJSON files, [`export`]
+//! * `server` - the `fib-server` HTTP JSON API, [`server`]
+//! * `store` - the on-disk result cache, [`store`]
+//! * `logging` - log files and the in-app log buffer, [`logging`]
+//! * `settings` - the TOML settings file read by every frontend, [`settings`]
+//! * `std` - floating-point geometry: [`lattice`] and [`visualization`]; the
+//!   shared memo [`cache`] and background [`worker`]
+//! * `alloc` - sequences, digit strings, [`heap`], [`validation`] and [`error`]
+//!
+//! With `default-features = false` the crate is `#![no_std]`: the Fibonacci,
+//! Zeckendorf, modular and closed-form sum routines need neither an operating
+//! system nor an allocator, so they run on microcontrollers. Enable `alloc` on
+//! targets with a global allocator to get the `Vec`- and `String`-returning APIs.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "egui-app")]
+pub mod app;
+#[cfg(feature = "std")]
+pub mod cache;
+#[cfg(feature = "cli")]
+pub mod cli;
+#[cfg(feature = "alloc")]
+pub mod error;
+#[cfg(feature = "export")]
+pub mod export;
+pub mod fibonacci;
+pub mod hashing;
+#[cfg(feature = "alloc")]
+pub mod heap;
+#[cfg(feature = "egui-app")]
+pub mod history;
+#[cfg(feature = "std")]
+pub mod lattice;
+#[cfg(feature = "logging")]
+pub mod logging;
+#[cfg(feature = "bigint")]
+pub mod phinary;
+#[cfg(feature = "bigint")]
+pub mod radix;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "egui-app")]
+pub mod session;
+#[cfg(feature = "settings")]
+pub mod settings;
+#[cfg(feature = "store")]
+pub mod store;
+pub mod sums;
+#[cfg(feature = "egui-app")]
+pub mod ui;
+#[cfg(feature = "alloc")]
+pub mod validation;
+#[cfg(feature = "std")]
+pub mod visualization;
+#[cfg(feature = "std")]
+pub mod worker;
+pub mod zeckendorf;
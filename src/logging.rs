@@ -0,0 +1,330 @@
+//! Leveled, structured logs for the frontends.
+//!
+//! Frontends call [`init`] once at startup. Events are written to a file in
+//! the platform data directory (`~/.local/share/fibonacci_sequence/logs` on
+//! Linux, or `FIBONACCI_LOG_DIR` when set) that rotates daily, keeping
+//! [`MAX_LOG_FILES`] days, and are also kept in [`LogBuffer::global`] for the
+//! egui app's log panel. In the browser only the buffer is filled.
+//!
+//! Which events are kept is set by a filter in `tracing`'s `EnvFilter`
+//! syntax, such as `debug` or `info,fibonacci_sequence::store=trace`, taken
+//! from the frontend's `--log` flag or the `FIBONACCI_LOG` variable.
+
+use std::collections::VecDeque;
+use std::fmt::{self, Write as _};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(target_arch = "wasm32")]
+use web_time::{SystemTime, UNIX_EPOCH};
+
+use directories::ProjectDirs;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+/// Environment variable holding the log filter
+pub const LOG_VAR: &str = "FIBONACCI_LOG";
+
+/// Environment variable overriding the log directory
+pub const LOG_DIR_VAR: &str = "FIBONACCI_LOG_DIR";
+
+/// Filter used when none is given or the given one is invalid
+pub const DEFAULT_FILTER: &str = "info";
+
+/// Days of log files kept
+pub const MAX_LOG_FILES: usize = 7;
+
+/// Most records kept in a [`LogBuffer`]; older ones are dropped
+pub const MAX_RECORDS: usize = 1000;
+
+/// One logged event
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub time: SystemTime,
+    pub level: Level,
+    /// Module the event came from, e.g. `fibonacci_sequence::app`
+    pub target: String,
+    /// The message and fields, prefixed with the enclosing spans
+    pub message: String,
+}
+
+impl Record {
+    /// Time of day in UTC as `HH:MM:SS`
+    pub fn clock(&self) -> String {
+        let seconds = self.time.duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+        format!("{:02}:{:02}:{:02}", seconds / 3600 % 24, seconds / 60 % 60, seconds % 60)
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:>5} {}: {}", self.clock(), self.level, self.target, self.message)
+    }
+}
+
+/// The most recent log records, shared between threads
+///
+/// It is a `tracing` layer: add it to a subscriber to record events.
+#[derive(Debug, Clone, Default)]
+pub struct LogBuffer {
+    records: Arc<Mutex<VecDeque<Record>>>,
+}
+
+impl LogBuffer {
+    /// The buffer [`init`] records into
+    pub fn global() -> &'static LogBuffer {
+        static GLOBAL: OnceLock<LogBuffer> = OnceLock::new();
+        GLOBAL.get_or_init(LogBuffer::default)
+    }
+
+    /// A copy of the records, oldest first
+    pub fn records(&self) -> Vec<Record> {
+        self.lock().iter().cloned().collect()
+    }
+
+    /// Number of records held
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Check if nothing has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Drop every record
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn push(&self, record: Record) {
+        let mut records = self.lock();
+        if records.len() == MAX_RECORDS {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<Record>> {
+        // A panic while pushing leaves the queue intact
+        self.records.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A span's fields as `name{a=1 b=2}`, kept in the span's extensions
+struct SpanLabel(String);
+
+impl<S> Layer<S> for LogBuffer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        let label = match fields.0.trim_start() {
+            "" => attrs.metadata().name().to_string(),
+            fields => format!("{}{{{}}}", attrs.metadata().name(), fields),
+        };
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanLabel(label));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut message = String::new();
+        for span in ctx.event_scope(event).into_iter().flat_map(|scope| scope.from_root()) {
+            if let Some(SpanLabel(label)) = span.extensions().get::<SpanLabel>() {
+                let _ = write!(message, "{}: ", label);
+            }
+        }
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        message.push_str(fields.0.trim_start());
+
+        let metadata = event.metadata();
+        self.push(Record {
+            time: SystemTime::now(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message,
+        });
+    }
+}
+
+/// Formats the message first, then the other fields as `name=value`
+#[derive(Default)]
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.0.insert_str(0, value);
+        } else {
+            let _ = write!(self.0, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.0.insert_str(0, &format!("{:?}", value));
+        } else {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// `FIBONACCI_LOG_DIR`, or `logs` in the platform data directory if unset
+pub fn default_dir() -> Option<PathBuf> {
+    match std::env::var_os(LOG_DIR_VAR) {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => ProjectDirs::from("org", "fibonacci", "fibonacci_sequence")
+            .map(|dirs| dirs.data_dir().join("logs")),
+    }
+}
+
+/// Parse a filter, falling back to [`DEFAULT_FILTER`]
+///
+/// # Returns
+/// The filter and, if `directives` was rejected, the reason
+pub fn parse_filter(directives: &str) -> (EnvFilter, Option<String>) {
+    match EnvFilter::try_new(directives) {
+        Ok(filter) => (filter, None),
+        Err(error) => (EnvFilter::new(DEFAULT_FILTER), Some(error.to_string())),
+    }
+}
+
+/// Install the global subscriber; calls after the first do nothing
+///
+/// `filter`, from a `--log` flag, takes precedence over `FIBONACCI_LOG`.
+///
+/// # Returns
+/// The directory log files are written to, or `None` if there is no
+/// writable one, in which case events only reach [`LogBuffer::global`]
+pub fn init(filter: Option<&str>) -> Option<PathBuf> {
+    static DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
+    DIR.get_or_init(|| {
+        let directives = filter
+            .map(str::to_string)
+            .or_else(|| std::env::var(LOG_VAR).ok().filter(|value| !value.is_empty()))
+            .unwrap_or_else(|| DEFAULT_FILTER.to_string());
+        let (env_filter, rejected) = parse_filter(&directives);
+        let (file, dir) = file_layer().unzip();
+
+        let _ = tracing_subscriber::registry()
+            .with(env_filter)
+            .with(LogBuffer::global().clone())
+            .with(file)
+            .try_init();
+
+        if let Some(reason) = rejected {
+            tracing::warn!(filter = %directives, %reason, "invalid log filter, using {}", DEFAULT_FILTER);
+        }
+        tracing::info!(version = env!("CARGO_PKG_VERSION"), dir = ?dir, "logging started");
+        dir
+    })
+    .clone()
+}
+
+/// A layer writing to the rotating file in [`default_dir`]
+#[cfg(not(target_arch = "wasm32"))]
+fn file_layer<S>() -> Option<(impl Layer<S>, PathBuf)>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    use tracing_appender::rolling::{RollingFileAppender, Rotation};
+    use tracing_subscriber::fmt::format::FmtSpan;
+
+    let dir = default_dir()?;
+    // The appender lists the directory to prune old files, and complains on
+    // stderr if it does not exist yet
+    std::fs::create_dir_all(&dir).ok()?;
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("fibonacci")
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(&dir)
+        .ok()?;
+    let layer = tracing_subscriber::fmt::layer()
+        .with_writer(appender)
+        // Closing spans logs how long computation and rendering took
+        .with_span_events(FmtSpan::CLOSE);
+    Some((layer, dir))
+}
+
+/// Browsers have no file system, so nothing is written
+#[cfg(target_arch = "wasm32")]
+fn file_layer<S>() -> Option<(tracing_subscriber::layer::Identity, PathBuf)> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing::{debug, info, info_span};
+
+    /// Run `f` with only `buffer` subscribed
+    fn capture(buffer: &LogBuffer, f: impl FnOnce()) {
+        let subscriber = tracing_subscriber::registry().with(buffer.clone());
+        tracing::subscriber::with_default(subscriber, f);
+    }
+
+    #[test]
+    fn test_records_spans_and_fields() {
+        let buffer = LogBuffer::default();
+        capture(&buffer, || {
+            info_span!("compute", n = 10).in_scope(|| info!(value = 55, "finished"));
+            debug!(name = "F(3)", "no span");
+        });
+
+        let records = buffer.records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].level, Level::INFO);
+        assert_eq!(records[0].target, module_path!());
+        assert_eq!(records[0].message, "compute{n=10}: finished value=55");
+        assert_eq!(records[1].message, "no span name=F(3)");
+        assert!(records[1].to_string().contains("DEBUG"));
+    }
+
+    #[test]
+    fn test_buffer_is_bounded() {
+        let buffer = LogBuffer::default();
+        capture(&buffer, || {
+            for i in 0..MAX_RECORDS + 5 {
+                info!(i);
+            }
+        });
+        assert_eq!(buffer.len(), MAX_RECORDS);
+        assert_eq!(buffer.records()[0].message, "i=5");
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        assert_eq!(parse_filter("debug,fibonacci_sequence::store=trace").1, None);
+        let (filter, rejected) = parse_filter("store=loud");
+        assert!(rejected.is_some());
+        assert_eq!(filter.to_string(), DEFAULT_FILTER);
+    }
+
+    #[test]
+    fn test_record_clock() {
+        let record = Record {
+            time: UNIX_EPOCH + std::time::Duration::from_secs(86_400 + 3_723),
+            level: Level::WARN,
+            target: "app".to_string(),
+            message: "slow".to_string(),
+        };
+        assert_eq!(record.clock(), "01:02:03");
+        assert_eq!(record.to_string(), "01:02:03  WARN app: slow");
+    }
+}
@@ -1,14 +1,19 @@
-mod app;
-mod fibonacci;
-mod ui;
-mod visualization;
-
-use app::FibonacciApp;
+use fibonacci_sequence::app::FibonacciApp;
+use fibonacci_sequence::settings::Settings;
+#[cfg(not(target_arch = "wasm32"))]
+use fibonacci_sequence::store::DiskCache;
 
+#[cfg(not(target_arch = "wasm32"))]
 fn main() -> Result<(), eframe::Error> {
+    fibonacci_sequence::logging::init(log_flag().as_deref());
+    let settings = Settings::load_default().unwrap_or_else(|error| {
+        tracing::warn!(path = ?Settings::default_path(), %error, "using default settings");
+        Settings::default()
+    });
+
     let options = eframe::NativeOptions {
         viewport: eframe::egui::ViewportBuilder::default()
-            .with_inner_size([800.0, 600.0])
+            .with_inner_size([settings.window.width, settings.window.height])
             .with_title("Fibonacci Spiral Generator"),
         ..Default::default()
     };
@@ -16,7 +21,68 @@ fn main() -> Result<(), eframe::Error> {
     eframe::run_native(
         "Fibonacci Spiral Generator",
         options,
-        Box::new(|_cc| Ok(Box::new(FibonacciApp::default()))),
+        Box::new(|cc| {
+            let app = FibonacciApp::restore(cc.storage, settings);
+            // Share big results with `fib`; without a cache directory they stay in memory
+            Ok(Box::new(match DiskCache::open_default() {
+                Ok(store) => app.with_store(store),
+                Err(error) => {
+                    tracing::warn!(%error, "result cache unavailable");
+                    app
+                }
+            }))
+        }),
     )
 }
 
+/// The filter given as `--log <FILTER>` or `--log=<FILTER>`
+#[cfg(not(target_arch = "wasm32"))]
+fn log_flag() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--log" {
+            return args.next();
+        }
+        if let Some(filter) = arg.strip_prefix("--log=") {
+            return Some(filter.to_string());
+        }
+    }
+    None
+}
+
+/// Browser entry point: attach the app to the `fibonacci_canvas` element of index.html
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    use eframe::wasm_bindgen::JsCast as _;
+
+    // Only feeds the Logs window: there is no log file in the browser
+    fibonacci_sequence::logging::init(None);
+    wasm_bindgen_futures::spawn_local(async {
+        let document = eframe::web_sys::window()
+            .and_then(|window| window.document())
+            .expect("running in a browser page");
+        let canvas = document
+            .get_element_by_id("fibonacci_canvas")
+            .and_then(|element| element.dyn_into::<eframe::web_sys::HtmlCanvasElement>().ok())
+            .expect("index.html has a canvas with id fibonacci_canvas");
+
+        let started = eframe::WebRunner::new()
+            .start(
+                canvas,
+                eframe::WebOptions::default(),
+                Box::new(|cc| Ok(Box::new(FibonacciApp::restore(cc.storage, Settings::default())))),
+            )
+            .await;
+
+        // Replace the loading text with the outcome
+        if let Some(status) = document.get_element_by_id("loading_text") {
+            match started {
+                Ok(()) => status.remove(),
+                Err(error) => status.set_inner_html(&format!(
+                    "<p>The app has crashed. See the developer console for details.</p><p>{:?}</p>",
+                    error
+                )),
+            }
+        }
+    });
+}
@@ -0,0 +1,435 @@
+use alloc::{format, string::{String, ToString}};
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, Mul, Neg, Sub};
+
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{ToPrimitive, Zero};
+
+use crate::fibonacci;
+
+/// An exact number a + bφ with integer a and b
+///
+/// Every finite base-φ string denotes one of these, and Z[φ] is closed under
+/// addition, subtraction and multiplication, so phinary arithmetic is exact.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GoldenInteger {
+    pub a: BigInt,
+    pub b: BigInt,
+}
+
+impl GoldenInteger {
+    /// Create a + bφ
+    pub fn new(a: impl Into<BigInt>, b: impl Into<BigInt>) -> Self {
+        Self { a: a.into(), b: b.into() }
+    }
+
+    /// The integer n, i.e. n + 0φ
+    pub fn from_integer(n: impl Into<BigInt>) -> Self {
+        Self::new(n, 0)
+    }
+
+    /// φ^k for any integer k
+    pub fn phi_power(k: i64) -> Self {
+        match k {
+            0 => Self::new(1, 0),
+            // φ^k = F(k-1) + F(k)φ
+            1.. => {
+                let (prev, current) = fibonacci::fib_big_pair(k as u64 - 1);
+                Self::new(prev, current)
+            }
+            // φ^-j = (-1)^j (F(j+1) - F(j)φ)
+            _ => {
+                let j = k.unsigned_abs();
+                let (current, next) = fibonacci::fib_big_pair(j);
+                let (a, b) = (BigInt::from(next), -BigInt::from(current));
+                if j.is_multiple_of(2) { Self { a, b } } else { Self { a: -a, b: -b } }
+            }
+        }
+    }
+
+    /// Check if this is zero
+    pub fn is_zero(&self) -> bool {
+        self.a.is_zero() && self.b.is_zero()
+    }
+
+    /// Compare with zero exactly
+    pub fn signum(&self) -> Ordering {
+        // 2(a + bφ) = (2a + b) + b√5; compare the two terms by squaring
+        let x = BigInt::from(2) * &self.a + &self.b;
+        let y = &self.b;
+        match (x.sign(), y.sign()) {
+            (Sign::NoSign, Sign::NoSign) => Ordering::Equal,
+            (Sign::Minus, Sign::Minus | Sign::NoSign) | (Sign::NoSign, Sign::Minus) => Ordering::Less,
+            (Sign::Plus, Sign::Plus | Sign::NoSign) | (Sign::NoSign, Sign::Plus) => Ordering::Greater,
+            (Sign::Plus, Sign::Minus) => (&x * &x).cmp(&(BigInt::from(5) * y * y)),
+            (Sign::Minus, Sign::Plus) => (BigInt::from(5) * y * y).cmp(&(&x * &x)),
+        }
+    }
+
+    /// The integer value, if b is zero
+    pub fn to_integer(&self) -> Option<BigInt> {
+        self.b.is_zero().then(|| self.a.clone())
+    }
+
+    /// Approximate value as a float
+    pub fn to_f64(&self) -> f64 {
+        self.a.to_f64().unwrap_or(f64::NAN) + self.b.to_f64().unwrap_or(f64::NAN) * fibonacci::PHI
+    }
+
+    /// Multiply by φ: (a + bφ)φ = b + (a + b)φ
+    fn times_phi(&self) -> Self {
+        Self { a: self.b.clone(), b: &self.a + &self.b }
+    }
+
+    /// Divide by φ: (a + bφ)/φ = (b - a) + aφ
+    fn over_phi(&self) -> Self {
+        Self { a: &self.b - &self.a, b: self.a.clone() }
+    }
+
+    /// Multiply by an integer
+    fn scale(&self, k: &BigInt) -> Self {
+        Self { a: &self.a * k, b: &self.b * k }
+    }
+}
+
+impl Add for &GoldenInteger {
+    type Output = GoldenInteger;
+
+    fn add(self, other: &GoldenInteger) -> GoldenInteger {
+        GoldenInteger { a: &self.a + &other.a, b: &self.b + &other.b }
+    }
+}
+
+impl Sub for &GoldenInteger {
+    type Output = GoldenInteger;
+
+    fn sub(self, other: &GoldenInteger) -> GoldenInteger {
+        GoldenInteger { a: &self.a - &other.a, b: &self.b - &other.b }
+    }
+}
+
+impl Mul for &GoldenInteger {
+    type Output = GoldenInteger;
+
+    fn mul(self, other: &GoldenInteger) -> GoldenInteger {
+        // φ² = φ + 1
+        let bd = &self.b * &other.b;
+        GoldenInteger {
+            a: &self.a * &other.a + &bd,
+            b: &self.a * &other.b + &self.b * &other.a + &bd,
+        }
+    }
+}
+
+impl Neg for &GoldenInteger {
+    type Output = GoldenInteger;
+
+    fn neg(self) -> GoldenInteger {
+        GoldenInteger { a: -&self.a, b: -&self.b }
+    }
+}
+
+impl fmt::Display for GoldenInteger {
+    /// Formats in standard base-φ form
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", expand(self, &BigInt::from(1), None).0)
+    }
+}
+
+/// Greedy base-φ expansion of numerator / denominator (denominator > 0)
+///
+/// Greedy digits are the standard form: taking φ^k whenever it fits can never
+/// leave room for φ^(k-1), since φ^k + φ^(k-1) = φ^(k+1) would have fit first.
+///
+/// # Returns
+/// The digit string and whether the expansion terminated exactly
+fn expand(numerator: &GoldenInteger, denominator: &BigInt, max_fraction_digits: Option<usize>) -> (String, bool) {
+    let negative = numerator.signum() == Ordering::Less;
+    let mut remainder = if negative { -numerator } else { numerator.clone() };
+    if remainder.is_zero() {
+        return ("0".to_string(), true);
+    }
+
+    // Find the largest k with denominator * φ^k <= remainder
+    let mut k = 0i64;
+    let mut power = GoldenInteger::phi_power(0).scale(denominator);
+    let fits = |p: &GoldenInteger, r: &GoldenInteger| (r - p).signum() != Ordering::Less;
+    if fits(&power, &remainder) {
+        loop {
+            let next = power.times_phi();
+            if !fits(&next, &remainder) {
+                break;
+            }
+            power = next;
+            k += 1;
+        }
+    } else {
+        while !fits(&power, &remainder) {
+            power = power.over_phi();
+            k -= 1;
+        }
+    }
+
+    let mut digits = String::new();
+    if negative {
+        digits.push('-');
+    }
+
+    let mut exponent = k.max(0);
+    if exponent > k {
+        // Leading zeros between the units digit and the first fractional 1
+        power = GoldenInteger::phi_power(exponent).scale(denominator);
+    }
+
+    loop {
+        if exponent < 0 {
+            if remainder.is_zero() {
+                break;
+            }
+            if max_fraction_digits.is_some_and(|max| exponent.unsigned_abs() as usize > max) {
+                return (digits, false);
+            }
+            if exponent == -1 {
+                digits.push('.');
+            }
+        }
+
+        if fits(&power, &remainder) {
+            remainder = &remainder - &power;
+            digits.push('1');
+        } else {
+            digits.push('0');
+        }
+
+        power = power.over_phi();
+        exponent -= 1;
+    }
+    (digits, true)
+}
+
+/// Convert an integer to standard base-φ form
+///
+/// # Examples
+/// ```
+/// use fibonacci_sequence::phinary::to_phinary;
+///
+/// assert_eq!(to_phinary(1), "1");
+/// assert_eq!(to_phinary(2), "10.01");
+/// assert_eq!(to_phinary(5), "1000.1001");
+/// ```
+pub fn to_phinary(n: u64) -> String {
+    to_phinary_big(&BigUint::from(n))
+}
+
+/// Convert an arbitrary-precision integer to standard base-φ form
+pub fn to_phinary_big(n: &BigUint) -> String {
+    GoldenInteger::from_integer(BigInt::from(n.clone())).to_string()
+}
+
+/// Convert numerator / denominator to base φ
+///
+/// Most rationals have infinite base-φ expansions, so the fractional part is
+/// truncated after `max_fraction_digits` digits.
+pub fn rational_to_phinary(numerator: i64, denominator: u64, max_fraction_digits: usize) -> Result<String, String> {
+    if denominator == 0 {
+        return Err("Denominator must not be zero".to_string());
+    }
+    let numerator = GoldenInteger::from_integer(numerator);
+    Ok(expand(&numerator, &BigInt::from(denominator), Some(max_fraction_digits)).0)
+}
+
+/// Parse a base-φ string into its exact value
+///
+/// Accepts an optional leading `-`, an optional radix point, and any decimal
+/// digit in each position, so non-standard strings like `"2"` or `"0.11"` parse too.
+pub fn parse_phinary(input: &str) -> Result<GoldenInteger, String> {
+    let input = input.trim();
+    let (negative, body) = match input.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, input),
+    };
+
+    let (integer, fraction) = body.split_once('.').unwrap_or((body, ""));
+    if integer.is_empty() && fraction.is_empty() {
+        return Err("Please enter a base-φ number".to_string());
+    }
+
+    let mut value = GoldenInteger::default();
+    let mut power = GoldenInteger::phi_power(integer.len() as i64 - 1);
+    for c in integer.chars().chain(fraction.chars()) {
+        let digit = c
+            .to_digit(10)
+            .ok_or_else(|| format!("Invalid base-φ digit '{}'", c))?;
+        if digit > 0 {
+            value = &value + &power.scale(&BigInt::from(digit));
+        }
+        power = power.over_phi();
+    }
+
+    Ok(if negative { -&value } else { value })
+}
+
+/// Rewrite a base-φ string in standard form (digits 0 and 1, no `11`)
+///
+/// # Examples
+/// ```
+/// use fibonacci_sequence::phinary::normalize;
+///
+/// assert_eq!(normalize("011").unwrap(), "100");
+/// assert_eq!(normalize("2").unwrap(), "10.01");
+/// ```
+pub fn normalize(input: &str) -> Result<String, String> {
+    Ok(parse_phinary(input)?.to_string())
+}
+
+/// Add two base-φ numbers, returning the standard form
+pub fn phinary_add(a: &str, b: &str) -> Result<String, String> {
+    Ok((&parse_phinary(a)? + &parse_phinary(b)?).to_string())
+}
+
+/// Subtract two base-φ numbers, returning the standard form
+pub fn phinary_sub(a: &str, b: &str) -> Result<String, String> {
+    Ok((&parse_phinary(a)? - &parse_phinary(b)?).to_string())
+}
+
+/// Multiply two base-φ numbers, returning the standard form
+pub fn phinary_mul(a: &str, b: &str) -> Result<String, String> {
+    Ok((&parse_phinary(a)? * &parse_phinary(b)?).to_string())
+}
+
+/// Convert a base-φ string to an integer, failing if its value is irrational
+pub fn phinary_to_integer(input: &str) -> Result<BigInt, String> {
+    parse_phinary(input)?
+        .to_integer()
+        .ok_or_else(|| format!("{} is not an integer", input.trim()))
+}
+
+/// Approximate value of a base-φ string
+pub fn phinary_to_f64(input: &str) -> Result<f64, String> {
+    Ok(parse_phinary(input)?.to_f64())
+}
+
+/// Check that a base-φ string is in standard form (digits 0 and 1, no `11`)
+pub fn is_standard_form(input: &str) -> bool {
+    let digits = input.strip_prefix('-').unwrap_or(input);
+    digits.chars().all(|c| matches!(c, '0' | '1' | '.'))
+        && digits.matches('.').count() <= 1
+        && !digits.replace('.', "").contains("11")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phi_powers() {
+        let phi = (1.0 + 5.0_f64.sqrt()) / 2.0;
+        for k in -20..=20 {
+            let exact = GoldenInteger::phi_power(k).to_f64();
+            assert!((exact - phi.powi(k as i32)).abs() < 1e-6 * phi.powi(k as i32).max(1.0));
+        }
+        assert_eq!(
+            &GoldenInteger::phi_power(5) * &GoldenInteger::phi_power(-5),
+            GoldenInteger::from_integer(1)
+        );
+    }
+
+    #[test]
+    fn test_signum() {
+        assert_eq!(GoldenInteger::new(0, 0).signum(), Ordering::Equal);
+        assert_eq!(GoldenInteger::new(-1, 1).signum(), Ordering::Greater); // 0.618
+        assert_eq!(GoldenInteger::new(2, -1).signum(), Ordering::Greater); // 0.382
+        assert_eq!(GoldenInteger::new(1, -1).signum(), Ordering::Less); // -0.618
+        assert_eq!(GoldenInteger::new(-2, 1).signum(), Ordering::Less); // -0.382
+        // F(n+1) - F(n)φ alternates in sign and shrinks towards zero
+        assert_eq!(GoldenInteger::new(89, -55).signum(), Ordering::Greater);
+        assert_eq!(GoldenInteger::new(144, -89).signum(), Ordering::Less);
+    }
+
+    #[test]
+    fn test_known_integers() {
+        let known = [
+            (0, "0"),
+            (1, "1"),
+            (2, "10.01"),
+            (3, "100.01"),
+            (4, "101.01"),
+            (5, "1000.1001"),
+            (6, "1010.0001"),
+            (7, "10000.0001"),
+            (10, "10100.0101"),
+        ];
+        for (n, expected) in known {
+            assert_eq!(to_phinary(n), expected, "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_integer_round_trip() {
+        for n in (0..500u64).chain([u64::MAX, 12200160415121876738]) {
+            let digits = to_phinary(n);
+            assert!(is_standard_form(&digits), "{} -> {}", n, digits);
+            assert_eq!(phinary_to_integer(&digits), Ok(BigInt::from(n)));
+        }
+    }
+
+    #[test]
+    fn test_fibonacci_values_in_base_phi() {
+        // φ^n = F(n-1) + F(n)φ, and Lucas numbers L(n) = φ^n + ψ^n are "10...0.0...01"
+        let lucas_10 = 123u64;
+        assert_eq!(to_phinary(lucas_10), "10000000000.0000000001");
+        let f = fibonacci::fib_big(100);
+        assert_eq!(phinary_to_integer(&to_phinary_big(&f)), Ok(BigInt::from(f)));
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("11").unwrap(), "100");
+        assert_eq!(normalize("0.11").unwrap(), "1");
+        assert_eq!(normalize("0011.0110").unwrap(), "100.1");
+        assert_eq!(normalize("2").unwrap(), "10.01");
+        assert_eq!(normalize("-0.1").unwrap(), "-0.1");
+        assert_eq!(normalize("000").unwrap(), "0");
+        assert!(normalize("1.2.3").is_err());
+        assert!(normalize("").is_err());
+        assert!(normalize("abc").is_err());
+    }
+
+    #[test]
+    fn test_arithmetic_matches_integers() {
+        for x in 0..30i64 {
+            for y in 0..30i64 {
+                let (a, b) = (to_phinary(x as u64), to_phinary(y as u64));
+                assert_eq!(phinary_to_integer(&phinary_add(&a, &b).unwrap()), Ok(BigInt::from(x + y)));
+                assert_eq!(phinary_to_integer(&phinary_sub(&a, &b).unwrap()), Ok(BigInt::from(x - y)));
+                assert_eq!(phinary_to_integer(&phinary_mul(&a, &b).unwrap()), Ok(BigInt::from(x * y)));
+            }
+        }
+        // φ * φ = φ + 1
+        assert_eq!(phinary_mul("10", "10").unwrap(), "100");
+        assert_eq!(phinary_add("10", "1").unwrap(), "100");
+        assert!(phinary_to_integer("10").is_err());
+    }
+
+    #[test]
+    fn test_rational_to_phinary() {
+        let phi = (1.0 + 5.0_f64.sqrt()) / 2.0;
+        for (p, q) in [(1i64, 2u64), (1, 3), (-7, 5), (22, 7), (100, 1)] {
+            let digits = rational_to_phinary(p, q, 40).unwrap();
+            let value = phinary_to_f64(&digits).unwrap();
+            assert!(
+                (value - p as f64 / q as f64).abs() <= phi.powi(-38),
+                "{}/{} -> {} = {}",
+                p,
+                q,
+                digits,
+                value
+            );
+            assert!(is_standard_form(&digits));
+        }
+        assert_eq!(rational_to_phinary(1, 0, 10), Err("Denominator must not be zero".to_string()));
+        assert_eq!(rational_to_phinary(2, 1, 0).unwrap(), "10");
+        assert_eq!(rational_to_phinary(2, 1, 2).unwrap(), "10.01");
+    }
+}
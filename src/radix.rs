@@ -0,0 +1,127 @@
+use alloc::{format, string::{String, ToString}};
+
+use num_bigint::BigUint;
+use num_traits::Num;
+
+use crate::{fibonacci, phinary, zeckendorf};
+
+/// Number bases offered for displaying Fibonacci values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "egui-app", derive(serde::Serialize, serde::Deserialize))]
+pub enum Base {
+    #[default]
+    Decimal,
+    Binary,
+    Hexadecimal,
+    /// Standard base-φ form
+    Phi,
+    /// Zeckendorf (Fibonacci-base) digits
+    Fibonacci,
+}
+
+impl Base {
+    /// All bases, in display order
+    pub const ALL: [Base; 5] = [
+        Base::Decimal,
+        Base::Binary,
+        Base::Hexadecimal,
+        Base::Phi,
+        Base::Fibonacci,
+    ];
+
+    /// Short label for selector buttons
+    pub fn label(&self) -> &'static str {
+        match self {
+            Base::Decimal => "10",
+            Base::Binary => "2",
+            Base::Hexadecimal => "16",
+            Base::Phi => "φ",
+            Base::Fibonacci => "Fibonacci",
+        }
+    }
+}
+
+/// Format a value in any radix from 2 to 36
+///
+/// # Examples
+/// ```
+/// use num_bigint::BigUint;
+/// use fibonacci_sequence::radix::to_radix;
+///
+/// assert_eq!(to_radix(&BigUint::from(55u32), 2).unwrap(), "110111");
+/// assert_eq!(to_radix(&BigUint::from(6765u32), 16).unwrap(), "1a6d");
+/// ```
+pub fn to_radix(value: &BigUint, radix: u32) -> Result<String, String> {
+    if !(2..=36).contains(&radix) {
+        return Err(format!("Radix {} is out of range! Please use 2-36", radix));
+    }
+    Ok(value.to_str_radix(radix))
+}
+
+/// Parse digits in any radix from 2 to 36
+pub fn from_radix(digits: &str, radix: u32) -> Result<BigUint, String> {
+    if !(2..=36).contains(&radix) {
+        return Err(format!("Radix {} is out of range! Please use 2-36", radix));
+    }
+    BigUint::from_str_radix(digits.trim(), radix)
+        .map_err(|_| format!("'{}' is not a valid base-{} number", digits.trim(), radix))
+}
+
+/// Format F(n) in any radix from 2 to 36
+pub fn fib_in_radix(n: u64, radix: u32) -> Result<String, String> {
+    to_radix(&fibonacci::fib_big(n), radix)
+}
+
+/// Format a value in one of the display bases
+pub fn format_in_base(value: &BigUint, base: Base) -> String {
+    match base {
+        Base::Decimal => value.to_string(),
+        Base::Binary => value.to_str_radix(2),
+        Base::Hexadecimal => value.to_str_radix(16),
+        Base::Phi => phinary::to_phinary_big(value),
+        Base::Fibonacci => zeckendorf::to_fibonacci_base_big(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_radix_round_trip() {
+        for n in [0u64, 1, 2, 55, 6765, u64::MAX] {
+            let value = BigUint::from(n);
+            for radix in 2..=36 {
+                let digits = to_radix(&value, radix).unwrap();
+                assert_eq!(from_radix(&digits, radix), Ok(value.clone()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_radix_errors() {
+        assert!(to_radix(&BigUint::from(5u32), 1).is_err());
+        assert!(to_radix(&BigUint::from(5u32), 37).is_err());
+        assert!(from_radix("102", 2).is_err());
+        assert!(from_radix("", 10).is_err());
+    }
+
+    #[test]
+    fn test_fib_in_radix() {
+        assert_eq!(fib_in_radix(10, 10).unwrap(), "55");
+        assert_eq!(fib_in_radix(12, 2).unwrap(), "10010000");
+        assert_eq!(fib_in_radix(93, 16).unwrap(), format!("{:x}", 12200160415121876738u64));
+        assert_eq!(fib_in_radix(300, 36).unwrap(), fibonacci::fib_big(300).to_str_radix(36));
+    }
+
+    #[test]
+    fn test_format_in_base() {
+        let value = BigUint::from(55u32);
+        assert_eq!(format_in_base(&value, Base::Decimal), "55");
+        assert_eq!(format_in_base(&value, Base::Binary), "110111");
+        assert_eq!(format_in_base(&value, Base::Hexadecimal), "37");
+        // 55 = F(10), and the lowest digit stands for F(2)
+        assert_eq!(format_in_base(&value, Base::Fibonacci), "100000000");
+        assert_eq!(phinary::phinary_to_integer(&format_in_base(&value, Base::Phi)), Ok(55.into()));
+    }
+}
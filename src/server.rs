@@ -0,0 +1,302 @@
+//! Local HTTP JSON API for Fibonacci computations.
+//!
+//! Routing is a pure function from method and URL to [`Response`], so it can be
+//! tested without sockets; [`Server`] only moves requests between `tiny_http`
+//! and [`handle`]. Every parameter goes through [`crate::validation`], which
+//! bounds the work a single request can cause.
+//!
+//! Fibonacci values, and every number `/mod` returns, are decimal strings,
+//! because they outgrow the 2^53 integers most JSON parsers handle exactly.
+
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs};
+
+use serde_json::{Value, json};
+
+use crate::error::InputError;
+use crate::validation::{self, MAX_BIG_DIGITS, MAX_BIG_N, MAX_SEQUENCE_TERMS};
+use crate::{fibonacci, zeckendorf};
+
+/// A JSON response
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+/// Route a request
+///
+/// # Arguments
+/// * `method` - HTTP method, only `GET` is served
+/// * `url` - Path with an optional query string, e.g. `/seq?from=0&to=10`
+pub fn handle(method: &str, url: &str) -> Response {
+    if method != "GET" {
+        return Response::error(405, format!("Method {} is not allowed", method));
+    }
+
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    let result = match segments.as_slice() {
+        ["fib", n] => fib(n),
+        ["seq"] => seq(query),
+        ["mod", n, m] => modulo(n, m),
+        ["is", x] => is(x),
+        ["zeckendorf", x] => zeckendorf(x),
+        ["openapi.json"] => Ok(openapi()),
+        _ => return Response::error(404, format!("No route for {}", path)),
+    };
+    match result {
+        Ok(body) => Response::ok(body),
+        Err(error) => Response::error(400, error.to_string()),
+    }
+}
+
+/// A parameter exactly as sent, without the surrounding whitespace the
+/// validators would trim, so what is accepted matches the OpenAPI patterns
+fn untrimmed(value: &str) -> Result<&str, InputError> {
+    if value.trim() != value {
+        return Err(InputError::NotANumber);
+    }
+    Ok(value)
+}
+
+fn fib(n: &str) -> Result<Value, InputError> {
+    let n = validation::validate_input_with_max(untrimmed(n)?, MAX_BIG_N)?;
+    Ok(json!({ "n": n, "value": fibonacci::fib_big(n as u64).to_string() }))
+}
+
+fn seq(query: &str) -> Result<Value, InputError> {
+    let param = |name: &'static str| {
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+            .ok_or(InputError::MissingParameter { name })
+            .and_then(untrimmed)
+    };
+    let (from, to) = validation::validate_range(param("from")?, param("to")?, MAX_BIG_N)?;
+
+    let (mut a, mut b) = fibonacci::fib_big_pair(from as u64);
+    let mut values = Vec::with_capacity((to - from + 1) as usize);
+    for _ in from..=to {
+        values.push(a.to_string());
+        let next = &a + &b;
+        (a, b) = (b, next);
+    }
+    Ok(json!({ "from": from, "to": to, "values": values }))
+}
+
+fn modulo(n: &str, m: &str) -> Result<Value, InputError> {
+    let n = validation::validate_value(untrimmed(n)?)?;
+    let m = validation::validate_modulus(untrimmed(m)?)?;
+    let value = fibonacci::fib_mod(n, m).ok_or(InputError::ZeroModulus)?;
+    Ok(json!({ "n": n.to_string(), "modulus": m.to_string(), "value": value.to_string() }))
+}
+
+fn is(x: &str) -> Result<Value, InputError> {
+    let value = validation::validate_big_value(untrimmed(x)?)?;
+    let index = fibonacci::fibonacci_index_big(&value);
+    Ok(json!({ "value": value.to_string(), "fibonacci": index.is_some(), "index": index }))
+}
+
+fn zeckendorf(x: &str) -> Result<Value, InputError> {
+    let value = validation::validate_big_value(untrimmed(x)?)?;
+    let indices = zeckendorf::zeckendorf_big(&value);
+    let terms: Vec<String> = indices.iter().map(|&i| fibonacci::fib_big(i as u64).to_string()).collect();
+    Ok(json!({
+        "value": value.to_string(),
+        "indices": indices,
+        "terms": terms,
+        "digits": zeckendorf::to_fibonacci_base_big(&value),
+    }))
+}
+
+/// The OpenAPI 3 description of every route, served at `/openapi.json`
+pub fn openapi() -> Value {
+    let error = json!({ "$ref": "#/components/schemas/Error" });
+    let responses = |ok: Value| {
+        json!({
+            "200": { "description": "Success", "content": { "application/json": { "schema": ok } } },
+            "400": { "description": "Invalid or out-of-range parameter", "content": { "application/json": { "schema": error } } },
+        })
+    };
+    let path_param = |name: &str, description: String| {
+        json!({ "name": name, "in": "path", "required": true, "description": description, "schema": { "type": "string", "pattern": "^[0-9]+$" } })
+    };
+    let query_param = |name: &str, description: &str| {
+        json!({ "name": name, "in": "query", "required": true, "description": description, "schema": { "type": "integer", "minimum": 0, "maximum": MAX_BIG_N } })
+    };
+    let decimal = json!({ "type": "string", "pattern": "^[0-9]+$" });
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Fibonacci API",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "Fibonacci numbers as JSON. Fibonacci values are decimal strings.",
+        },
+        "paths": {
+            "/fib/{n}": { "get": {
+                "summary": "F(n)",
+                "parameters": [path_param("n", format!("Index, 0-{}", MAX_BIG_N))],
+                "responses": responses(json!({ "type": "object", "properties": {
+                    "n": { "type": "integer" }, "value": decimal } })),
+            } },
+            "/seq": { "get": {
+                "summary": "F(from) through F(to)",
+                "parameters": [
+                    query_param("from", "First index"),
+                    query_param("to", &format!("Last index, at most {} terms after from", MAX_SEQUENCE_TERMS - 1)),
+                ],
+                "responses": responses(json!({ "type": "object", "properties": {
+                    "from": { "type": "integer" }, "to": { "type": "integer" },
+                    "values": { "type": "array", "items": decimal } } })),
+            } },
+            "/mod/{n}/{m}": { "get": {
+                "summary": "F(n) mod m",
+                "parameters": [
+                    path_param("n", "Index, any u64".to_string()),
+                    path_param("m", "Modulus, 1 or more".to_string()),
+                ],
+                "responses": responses(json!({ "type": "object", "properties": {
+                    "n": decimal, "modulus": decimal, "value": decimal } })),
+            } },
+            "/is/{x}": { "get": {
+                "summary": "Whether x is a Fibonacci number",
+                "parameters": [path_param("x", format!("Value with at most {} digits", MAX_BIG_DIGITS))],
+                "responses": responses(json!({ "type": "object", "properties": {
+                    "value": decimal, "fibonacci": { "type": "boolean" },
+                    "index": { "type": "integer", "nullable": true } } })),
+            } },
+            "/zeckendorf/{x}": { "get": {
+                "summary": "Zeckendorf decomposition of x",
+                "parameters": [path_param("x", format!("Value with at most {} digits", MAX_BIG_DIGITS))],
+                "responses": responses(json!({ "type": "object", "properties": {
+                    "value": decimal,
+                    "indices": { "type": "array", "items": { "type": "integer" } },
+                    "terms": { "type": "array", "items": decimal },
+                    "digits": { "type": "string", "pattern": "^[01]+$" } } })),
+            } },
+            "/openapi.json": { "get": {
+                "summary": "This document",
+                "responses": { "200": { "description": "OpenAPI 3 document" } },
+            } },
+        },
+        "components": { "schemas": { "Error": {
+            "type": "object", "properties": { "error": { "type": "string" } }, "required": ["error"],
+        } } },
+    })
+}
+
+/// A bound HTTP server
+pub struct Server {
+    http: tiny_http::Server,
+}
+
+impl Server {
+    /// Bind to an address such as `127.0.0.1:8080`; port 0 picks a free port
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let http = tiny_http::Server::http(addr).map_err(io::Error::other)?;
+        Ok(Self { http })
+    }
+
+    /// The address the server listens on
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.http.server_addr().to_ip()
+    }
+
+    /// Serve requests until [`Server::shutdown`] is called
+    pub fn run(&self) {
+        for request in self.http.incoming_requests() {
+            let response = handle(request.method().as_str(), request.url());
+            let header = tiny_http::Header::from_bytes("Content-Type", "application/json")
+                .expect("static header is valid");
+            let reply = tiny_http::Response::from_string(response.body.to_string())
+                .with_status_code(response.status)
+                .with_header(header);
+            // The client may have gone away; that only affects its own request
+            let _ = request.respond(reply);
+        }
+    }
+
+    /// Make [`Server::run`] return, from any thread
+    pub fn shutdown(&self) {
+        self.http.unblock();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes() {
+        assert_eq!(handle("GET", "/fib/10").body, json!({ "n": 10, "value": "55" }));
+        assert_eq!(
+            handle("GET", "/seq?from=3&to=6").body,
+            json!({ "from": 3, "to": 6, "values": ["2", "3", "5", "8"] })
+        );
+        assert_eq!(
+            handle("GET", "/mod/1000000000000/10").body,
+            json!({ "n": "1000000000000", "modulus": "10", "value": "5" })
+        );
+        assert_eq!(handle("GET", "/mod/5/18446744073709551615").body["modulus"], "18446744073709551615");
+        assert_eq!(handle("GET", "/is/144").body, json!({ "value": "144", "fibonacci": true, "index": 12 }));
+        assert_eq!(handle("GET", "/is/145").body["index"], Value::Null);
+        assert_eq!(handle("GET", "/zeckendorf/100").body["indices"], json!([11, 6, 4]));
+    }
+
+    #[test]
+    fn test_limits_reuse_validation() {
+        let response = handle("GET", "/fib/1000000000000");
+        assert_eq!(response.status, 400);
+        assert_eq!(response.body["error"], validation::validate_input_with_max("1000000000000", MAX_BIG_N).unwrap_err().to_string());
+
+        assert_eq!(handle("GET", "/seq?from=0&to=5000").status, 400);
+        assert_eq!(handle("GET", "/seq?from=5").body["error"], "Missing query parameter 'to'");
+        assert_eq!(handle("GET", "/mod/5/0").status, 400);
+        assert_eq!(handle("GET", &format!("/is/{}", "9".repeat(MAX_BIG_DIGITS + 1))).status, 400);
+    }
+
+    #[test]
+    fn test_parameters_match_openapi_pattern() {
+        // Only what `^[0-9]+$` allows gets through
+        for url in ["/fib/+5", "/fib/ 5", "/mod/+5/7", "/mod/5/7 ", "/is/+8", "/zeckendorf/ 8", "/seq?from= 0&to=5"] {
+            assert_eq!(handle("GET", url).status, 400, "{}", url);
+        }
+        assert_eq!(handle("GET", "/fib/007").body["value"], "13");
+    }
+
+    #[test]
+    fn test_unknown_routes_and_methods() {
+        assert_eq!(handle("GET", "/").status, 404);
+        assert_eq!(handle("GET", "/fib").status, 404);
+        assert_eq!(handle("GET", "/fib/1/2").status, 404);
+        assert_eq!(handle("POST", "/fib/10").status, 405);
+    }
+
+    #[test]
+    fn test_openapi_lists_every_route() {
+        let spec = openapi();
+        assert_eq!(spec["openapi"], "3.0.3");
+        for path in ["/fib/{n}", "/seq", "/mod/{n}/{m}", "/is/{x}", "/zeckendorf/{x}", "/openapi.json"] {
+            assert!(spec["paths"][path]["get"].is_object(), "{} missing", path);
+        }
+        assert_eq!(handle("GET", "/openapi.json").body, spec);
+    }
+}
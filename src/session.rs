@@ -0,0 +1,148 @@
+//! What the egui app remembers between runs.
+//!
+//! eframe calls `App::save` every [`SAVE_INTERVAL`] and on exit, storing a
+//! [`Session`] as JSON under [`STORAGE_KEY`] in its storage: a file in the
+//! platform data directory natively, `localStorage` in the browser. eframe
+//! keeps the window size and position and where dialogs were itself.
+//!
+//! Every save records [`VERSION`]. When the layout changes, the version is
+//! bumped and [`Session::from_json`] upgrades older saves; saves from newer
+//! versions are ignored.
+//!
+//! | Version | Change                                                      |
+//! |---------|-------------------------------------------------------------|
+//! | 1       | First layout                                                |
+//! | 2       | History entries gain `value`, `time` and `pinned`           |
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::fibonacci::{self, Algorithm};
+use crate::history::History;
+use crate::radix::Base;
+use crate::ui::VisualizationMode;
+use crate::visualization::utils::SequenceFormat;
+
+/// Key the session is stored under
+pub const STORAGE_KEY: &str = "fibonacci_session";
+
+/// Version of the session layout written by this build
+pub const VERSION: u32 = 2;
+
+/// How often the session is saved while the app runs
+pub const SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Which windows are open
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Layout {
+    pub export_open: bool,
+    pub logs_open: bool,
+    pub history_open: bool,
+}
+
+/// The app state saved between runs
+///
+/// Missing fields take their defaults, so a save only needs a migration
+/// when the meaning of a field changes.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub version: u32,
+    /// Text in the input box
+    pub input: String,
+    /// Past calculations
+    pub history: History,
+    /// Selected algorithm; `None` uses the one from the settings file
+    pub algorithm: Option<Algorithm>,
+    pub base: Base,
+    pub sequence_format: SequenceFormat,
+    pub visualization: VisualizationMode,
+    pub layout: Layout,
+}
+
+impl Session {
+    /// The session as stored
+    pub fn to_json(&self) -> String {
+        let session = Session { version: VERSION, ..self.clone() };
+        // Plain data with string keys always serializes
+        serde_json::to_string(&session).unwrap_or_default()
+    }
+
+    /// Read a stored session, upgrading older versions
+    ///
+    /// # Errors
+    /// Returns why the text could not be used: it is not a session, or it
+    /// was written by a newer version
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let mut value: Value = serde_json::from_str(json).map_err(|error| error.to_string())?;
+        let version = value.get("version").and_then(Value::as_u64).unwrap_or(0);
+        if version > u64::from(VERSION) {
+            return Err(format!("saved by a newer version ({} > {})", version, VERSION));
+        }
+        migrate(&mut value, version);
+        let mut session: Session = serde_json::from_value(value).map_err(|error| error.to_string())?;
+        session.version = VERSION;
+        Ok(session)
+    }
+}
+
+/// Bring a stored session from `version` up to [`VERSION`]
+fn migrate(value: &mut Value, version: u64) {
+    if version < 2 {
+        // Version 1 stored only n and the algorithm; F(n) is recomputed and
+        // the time is unknown
+        let entries = value.get_mut("history").and_then(Value::as_array_mut);
+        for entry in entries.into_iter().flatten() {
+            let n = entry.get("n").and_then(Value::as_u64).and_then(|n| u32::try_from(n).ok());
+            if let (Some(fields), Some(value)) = (entry.as_object_mut(), n.and_then(fibonacci::fib_checked)) {
+                fields.insert("value".to_string(), value.into());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::HistoryEntry;
+
+    #[test]
+    fn test_round_trip() {
+        let mut session = Session {
+            input: "12".to_string(),
+            algorithm: Some(Algorithm::FastDoubling),
+            base: Base::Hexadecimal,
+            sequence_format: SequenceFormat::Table,
+            visualization: VisualizationMode::Sunflower,
+            layout: Layout { logs_open: true, history_open: true, ..Layout::default() },
+            ..Session::default()
+        };
+        session.history.record(HistoryEntry::new(12, Algorithm::FastDoubling, 144));
+
+        let json = session.to_json();
+        assert!(json.contains("\"version\":2"));
+        assert_eq!(Session::from_json(&json).unwrap(), Session { version: VERSION, ..session });
+    }
+
+    #[test]
+    fn test_versions() {
+        // Missing fields take their defaults
+        let session = Session::from_json(r#"{"version":2,"input":"7"}"#).unwrap();
+        assert_eq!(session.input, "7");
+        assert_eq!(session.base, Base::Decimal);
+        assert!(!session.layout.history_open);
+
+        // Version 1 history entries get their value back
+        let session = Session::from_json(r#"{"version":1,"history":[{"n":12,"algorithm":"memoized"}]}"#).unwrap();
+        let entry = session.history.entries()[0];
+        assert_eq!((entry.n, entry.algorithm, entry.value), (12, Algorithm::Memoized, 144));
+        assert_eq!(entry.time_label(), "earlier");
+
+        assert!(Session::from_json(r#"{"version":2,"base":"Nonsense"}"#).is_err());
+        assert!(Session::from_json(r#"{"version":99}"#).unwrap_err().contains("newer"));
+        assert!(Session::from_json("not json").is_err());
+    }
+}
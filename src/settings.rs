@@ -0,0 +1,330 @@
+//! User settings shared by the egui, GTK and command-line frontends.
+//!
+//! Settings live in a TOML file, `config.toml` in the platform config
+//! directory (`~/.config/fibonacci_sequence` on Linux) or the file named by
+//! `FIBONACCI_CONFIG`. Every key is optional, and a missing file means all
+//! defaults:
+//!
+//! ```toml
+//! # Calculation used for F(n): recursive, memoized, iterative or fast-doubling
+//! algorithm = "iterative"
+//!
+//! [limits]
+//! # Largest n the egui and GTK apps accept, 1-93 (1-40 with recursive)
+//! max_n = 25
+//!
+//! [display]
+//! # Spacing of the spiral's background grid in points, 2-100
+//! grid_size = 10.0
+//! # Fill colors of the spiral's squares, 1-16 of them
+//! colors = ["#FFFFC8", "#FFF5B4", "#FFEBA0", "#FFE18C", "#FFD778", "#FFCD64", "#FFC350", "#FFB93C"]
+//!
+//! [window]
+//! # Initial window size in points, 300-4000 wide and 200-4000 high
+//! width = 800.0
+//! height = 700.0
+//! ```
+//!
+//! Unknown keys and out-of-range values are rejected with a [`SettingsError`]
+//! naming the key, so a typo is never silently ignored.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+
+use crate::fibonacci::Algorithm;
+use crate::validation::{MAX_FIBONACCI_N, MAX_U64_N};
+
+/// Environment variable naming the settings file
+pub const CONFIG_VAR: &str = "FIBONACCI_CONFIG";
+
+/// Name of the settings file in the config directory
+pub const FILE_NAME: &str = "config.toml";
+
+/// The spiral's default colors, golden yellows from light to amber
+///
+/// The egui app's `visualization::FIBONACCI_COLORS` is built from these.
+pub const DEFAULT_COLORS: [Rgb; 8] = [
+    Rgb([255, 255, 200]), // Light yellow
+    Rgb([255, 245, 180]), // Cream
+    Rgb([255, 235, 160]), // Light gold
+    Rgb([255, 225, 140]), // Gold
+    Rgb([255, 215, 120]), // Darker gold
+    Rgb([255, 205, 100]), // Orange-gold
+    Rgb([255, 195, 80]),  // Deep gold
+    Rgb([255, 185, 60]),  // Amber
+];
+
+/// Most spiral colors accepted
+pub const MAX_COLORS: usize = 16;
+
+const HEADER: &str = "# Fibonacci Sequence Generator settings; see \"Configuration\" in the README\n\n";
+
+/// Everything read from the settings file
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub algorithm: Algorithm,
+    pub limits: Limits,
+    pub display: Display,
+    pub window: Window,
+}
+
+/// Input limits of the graphical frontends
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Limits {
+    /// Largest n accepted
+    pub max_n: u32,
+}
+
+/// How the spiral is drawn
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Display {
+    /// Spacing of the background grid in points
+    pub grid_size: f32,
+    /// Fill colors of the squares, repeated as needed
+    pub colors: Vec<Rgb>,
+}
+
+/// Initial window size in points
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Window {
+    pub width: f32,
+    pub height: f32,
+}
+
+/// A color written as `"#RRGGBB"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub [u8; 3]);
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self { max_n: MAX_FIBONACCI_N }
+    }
+}
+
+impl Default for Display {
+    fn default() -> Self {
+        Self { grid_size: 10.0, colors: DEFAULT_COLORS.to_vec() }
+    }
+}
+
+impl Default for Window {
+    fn default() -> Self {
+        Self { width: 800.0, height: 700.0 }
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [r, g, b] = self.0;
+        write!(f, "#{:02X}{:02X}{:02X}", r, g, b)
+    }
+}
+
+impl std::str::FromStr for Rgb {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("'{}' is not a #RRGGBB color", s);
+        let hex = s.strip_prefix('#').filter(|hex| hex.len() == 6).ok_or_else(invalid)?;
+        let channel = |i: usize| hex.get(i..i + 2).and_then(|c| u8::from_str_radix(c, 16).ok()).ok_or_else(invalid);
+        Ok(Rgb([channel(0)?, channel(2)?, channel(4)?]))
+    }
+}
+
+impl Serialize for Rgb {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rgb {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Why settings could not be loaded or saved
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingsError {
+    /// The file exists but could not be read or written
+    Io(String),
+    /// The file is not valid TOML or has unknown keys or wrong types
+    Syntax(String),
+    /// A value is out of range, e.g. `key` is `"limits.max_n"`
+    Invalid { key: &'static str, reason: String },
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsError::Io(reason) => write!(f, "{}", reason),
+            SettingsError::Syntax(reason) => write!(f, "{}", reason.trim_end()),
+            SettingsError::Invalid { key, reason } => write!(f, "{} {}", key, reason),
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+fn invalid(key: &'static str, reason: String) -> SettingsError {
+    SettingsError::Invalid { key, reason }
+}
+
+impl Settings {
+    /// `FIBONACCI_CONFIG`, or `config.toml` in the platform config directory if unset
+    pub fn default_path() -> Option<PathBuf> {
+        match std::env::var_os(CONFIG_VAR) {
+            Some(path) if !path.is_empty() => Some(PathBuf::from(path)),
+            _ => ProjectDirs::from("org", "fibonacci", "fibonacci_sequence")
+                .map(|dirs| dirs.config_dir().join(FILE_NAME)),
+        }
+    }
+
+    /// Parse and validate the contents of a settings file
+    pub fn from_toml(text: &str) -> Result<Self, SettingsError> {
+        let settings: Settings = toml::from_str(text).map_err(|error| SettingsError::Syntax(error.to_string()))?;
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    /// The settings as a TOML document
+    pub fn to_toml(&self) -> String {
+        let body = toml::to_string_pretty(self).expect("settings are plain TOML values");
+        format!("{}{}", HEADER, body)
+    }
+
+    /// Check every value is in range
+    pub fn validate(&self) -> Result<(), SettingsError> {
+        let max_n = self.algorithm.max_n().min(MAX_U64_N);
+        if !(1..=max_n).contains(&self.limits.max_n) {
+            return Err(invalid(
+                "limits.max_n",
+                format!("must be 1-{} with the {} algorithm, not {}", max_n, self.algorithm.name(), self.limits.max_n),
+            ));
+        }
+        if !(2.0..=100.0).contains(&self.display.grid_size) {
+            return Err(invalid("display.grid_size", format!("must be 2-100, not {}", self.display.grid_size)));
+        }
+        if !(1..=MAX_COLORS).contains(&self.display.colors.len()) {
+            return Err(invalid(
+                "display.colors",
+                format!("must list 1-{} colors, not {}", MAX_COLORS, self.display.colors.len()),
+            ));
+        }
+        if !(300.0..=4000.0).contains(&self.window.width) {
+            return Err(invalid("window.width", format!("must be 300-4000, not {}", self.window.width)));
+        }
+        if !(200.0..=4000.0).contains(&self.window.height) {
+            return Err(invalid("window.height", format!("must be 200-4000, not {}", self.window.height)));
+        }
+        Ok(())
+    }
+
+    /// Read a settings file; a missing file gives the defaults
+    pub fn load(path: &Path) -> Result<Self, SettingsError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::from_toml(&text),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(SettingsError::Io(error.to_string())),
+        }
+    }
+
+    /// Read the file at [`Settings::default_path`], or the defaults if there is none
+    pub fn load_default() -> Result<Self, SettingsError> {
+        match Self::default_path() {
+            Some(path) => Self::load(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Validate and write the settings, creating the directory if needed
+    pub fn save(&self, path: &Path) -> Result<(), SettingsError> {
+        self.validate()?;
+        let io_error = |error: io::Error| SettingsError::Io(error.to_string());
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        fs::write(path, self.to_toml()).map_err(io_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let settings = Settings::from_toml("").unwrap();
+        assert_eq!(settings, Settings::default());
+        assert_eq!(settings.limits.max_n, MAX_FIBONACCI_N);
+        assert_eq!(settings.algorithm, Algorithm::Iterative);
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn test_partial_file() {
+        let settings = Settings::from_toml(
+            "algorithm = \"fast-doubling\"\n[limits]\nmax_n = 60\n[display]\ncolors = [\"#ff0000\", \"#00FF00\"]\n",
+        )
+        .unwrap();
+        assert_eq!(settings.algorithm, Algorithm::FastDoubling);
+        assert_eq!(settings.limits.max_n, 60);
+        assert_eq!(settings.display.colors, vec![Rgb([255, 0, 0]), Rgb([0, 255, 0])]);
+        assert_eq!(settings.display.grid_size, 10.0);
+        assert_eq!(settings.window, Window::default());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut settings = Settings { algorithm: Algorithm::Memoized, ..Settings::default() };
+        settings.window.width = 1024.0;
+        let text = settings.to_toml();
+        assert!(text.starts_with(HEADER));
+        assert!(text.contains("algorithm = \"memoized\""));
+        assert!(text.contains("\"#FFFFC8\""));
+        assert_eq!(Settings::from_toml(&text), Ok(settings));
+    }
+
+    #[test]
+    fn test_validation() {
+        let error = |text: &str| Settings::from_toml(text).unwrap_err().to_string();
+        assert_eq!(error("[limits]\nmax_n = 94"), "limits.max_n must be 1-93 with the iterative algorithm, not 94");
+        assert_eq!(
+            error("algorithm = \"recursive\"\n[limits]\nmax_n = 41"),
+            "limits.max_n must be 1-40 with the recursive algorithm, not 41"
+        );
+        assert_eq!(error("[display]\ngrid_size = 0.5"), "display.grid_size must be 2-100, not 0.5");
+        assert_eq!(error("[display]\ncolors = []"), "display.colors must list 1-16 colors, not 0");
+        assert_eq!(error("[window]\nheight = 5000.0"), "window.height must be 200-4000, not 5000");
+        assert!(error("[display]\ncolors = [\"gold\"]").contains("'gold' is not a #RRGGBB color"));
+        assert!(error("algorithm = \"quantum\"").contains("unknown variant"));
+        assert!(error("[limits]\nmax_m = 3").contains("unknown field `max_m`"));
+        assert!(matches!(Settings::from_toml("[limits"), Err(SettingsError::Syntax(_))));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("fibonacci_settings_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("nested").join(FILE_NAME);
+        assert_eq!(Settings::load(&path), Ok(Settings::default()));
+
+        let mut settings = Settings::default();
+        settings.display.grid_size = 20.0;
+        settings.save(&path).unwrap();
+        assert_eq!(Settings::load(&path), Ok(settings.clone()));
+
+        settings.limits.max_n = 0;
+        assert!(matches!(settings.save(&path), Err(SettingsError::Invalid { key: "limits.max_n", .. })));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
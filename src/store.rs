@@ -0,0 +1,510 @@
+//! On-disk cache for expensive arbitrary-precision results.
+//!
+//! Every result is one small text file in the cache directory, named after
+//! its [`Key`]. A file starts with the format version and a CRC-32 of its
+//! contents, so entries written by another version or damaged on disk are
+//! never returned: reading one is a miss, [`DiskCache::verify`] reports it
+//! and [`DiskCache::prune`] deletes it. The directory stays under a byte limit
+//! by evicting the entries used longest ago.
+//!
+//! Everything stays on the local machine. The default directory is the
+//! platform cache directory (`~/.cache/fibonacci_sequence` on Linux), or
+//! `FIBONACCI_CACHE_DIR` when set.
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use directories::ProjectDirs;
+use num_bigint::BigUint;
+use tracing::{debug, info, warn};
+
+/// Version written to new entries; entries with any other version are ignored
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Default size limit of the cache directory
+pub const DEFAULT_MAX_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Smallest n worth storing; smaller results are quicker to recompute than to read
+pub const MIN_STORED_N: u64 = 10_000;
+
+/// Environment variable overriding the cache directory
+pub const CACHE_DIR_VAR: &str = "FIBONACCI_CACHE_DIR";
+
+const MAGIC: &str = "fibonacci-cache";
+const EXTENSION: &str = "fib";
+
+/// The sequence a cached value belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Sequence {
+    Fibonacci,
+    Lucas,
+}
+
+impl Sequence {
+    pub const ALL: [Sequence; 2] = [Sequence::Fibonacci, Sequence::Lucas];
+
+    /// Lowercase name used in file names
+    pub fn name(self) -> &'static str {
+        match self {
+            Sequence::Fibonacci => "fibonacci",
+            Sequence::Lucas => "lucas",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|sequence| sequence.name() == name)
+    }
+}
+
+/// What a cached value is: term `n` of `sequence`, optionally reduced mod `modulus`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Key {
+    pub sequence: Sequence,
+    pub modulus: Option<u64>,
+    pub n: u64,
+}
+
+impl Key {
+    /// Key of an exact term
+    pub fn new(sequence: Sequence, n: u64) -> Self {
+        Self { sequence, modulus: None, n }
+    }
+
+    /// Key of a term reduced mod `modulus`
+    pub fn modular(sequence: Sequence, n: u64, modulus: u64) -> Self {
+        Self { sequence, modulus: Some(modulus), n }
+    }
+
+    /// File name of the entry, e.g. `fibonacci-100000.fib` or `lucas-5-mod-7.fib`
+    pub fn file_name(&self) -> String {
+        match self.modulus {
+            Some(m) => format!("{}-{}-mod-{}.{}", self.sequence.name(), self.n, m, EXTENSION),
+            None => format!("{}-{}.{}", self.sequence.name(), self.n, EXTENSION),
+        }
+    }
+
+    /// Parse a name written by [`Key::file_name`]
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        let stem = name.strip_suffix(EXTENSION)?.strip_suffix('.')?;
+        let (stem, modulus) = match stem.split_once("-mod-") {
+            Some((stem, m)) => (stem, Some(m.parse().ok()?)),
+            None => (stem, None),
+        };
+        let (sequence, n) = stem.split_once('-')?;
+        let key = Key { sequence: Sequence::from_name(sequence)?, modulus, n: n.parse().ok()? };
+        // Reject aliases such as leading zeros, so every key has exactly one file
+        (key.file_name() == name).then_some(key)
+    }
+
+    fn line(&self) -> String {
+        match self.modulus {
+            Some(m) => format!("{} {} {}", self.sequence.name(), self.n, m),
+            None => format!("{} {}", self.sequence.name(), self.n),
+        }
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let letter = match self.sequence {
+            Sequence::Fibonacci => 'F',
+            Sequence::Lucas => 'L',
+        };
+        match self.modulus {
+            Some(m) => write!(f, "{}({}) mod {}", letter, self.n, m),
+            None => write!(f, "{}({})", letter, self.n),
+        }
+    }
+}
+
+/// Why a cache entry cannot be used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryError {
+    /// The file could not be read
+    Unreadable(String),
+    /// The file is not a cache entry
+    Malformed,
+    /// The entry was written in another format version
+    Version { found: u32 },
+    /// The contents do not match their checksum
+    Checksum,
+    /// The entry holds a different key than its file name says
+    WrongKey,
+}
+
+impl fmt::Display for EntryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntryError::Unreadable(reason) => write!(f, "cannot be read: {}", reason),
+            EntryError::Malformed => write!(f, "is not a cache entry"),
+            EntryError::Version { found } => {
+                write!(f, "has format version {}, expected {}", found, FORMAT_VERSION)
+            }
+            EntryError::Checksum => write!(f, "does not match its checksum"),
+            EntryError::WrongKey => write!(f, "holds a different key than its name"),
+        }
+    }
+}
+
+impl std::error::Error for EntryError {}
+
+/// A file in the cache directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryInfo {
+    pub key: Key,
+    pub path: PathBuf,
+    pub bytes: u64,
+    /// When the entry was last written or read
+    pub used: SystemTime,
+}
+
+/// What [`DiskCache::prune`] deleted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PruneReport {
+    pub removed: usize,
+    pub freed: u64,
+}
+
+fn encode(key: &Key, value: &BigUint) -> String {
+    let (line, digits) = (key.line(), value.to_string());
+    format!(
+        "{} {}\n{}\ncrc32 {:08x}\n{}\n",
+        MAGIC,
+        FORMAT_VERSION,
+        line,
+        checksum(&line, &digits),
+        digits
+    )
+}
+
+fn decode(contents: &str, key: &Key) -> Result<BigUint, EntryError> {
+    let mut lines = contents.lines();
+    let version = lines
+        .next()
+        .and_then(|line| line.strip_prefix(MAGIC)?.strip_prefix(' ')?.parse().ok())
+        .ok_or(EntryError::Malformed)?;
+    if version != FORMAT_VERSION {
+        return Err(EntryError::Version { found: version });
+    }
+    let (Some(line), Some(crc), Some(digits), None) = (lines.next(), lines.next(), lines.next(), lines.next())
+    else {
+        return Err(EntryError::Malformed);
+    };
+    let crc = crc
+        .strip_prefix("crc32 ")
+        .and_then(|crc| u32::from_str_radix(crc, 16).ok())
+        .ok_or(EntryError::Malformed)?;
+    if crc != checksum(line, digits) {
+        return Err(EntryError::Checksum);
+    }
+    if line != key.line() {
+        return Err(EntryError::WrongKey);
+    }
+    digits.parse().map_err(|_| EntryError::Malformed)
+}
+
+fn checksum(line: &str, digits: &str) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(line.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(digits.as_bytes());
+    hasher.finalize()
+}
+
+fn read_entry(path: &Path, key: &Key) -> Result<BigUint, EntryError> {
+    let contents = fs::read_to_string(path).map_err(|e| EntryError::Unreadable(e.to_string()))?;
+    decode(&contents, key)
+}
+
+/// A directory of cached results with a size limit
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl DiskCache {
+    /// Open (and create if needed) a cache directory
+    ///
+    /// # Arguments
+    /// * `dir` - Directory holding the entries
+    /// * `max_bytes` - Size the entries are kept under
+    pub fn open(dir: impl Into<PathBuf>, max_bytes: u64) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir, max_bytes })
+    }
+
+    /// `FIBONACCI_CACHE_DIR`, or the platform cache directory if unset
+    pub fn default_dir() -> Option<PathBuf> {
+        match std::env::var_os(CACHE_DIR_VAR) {
+            Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+            _ => ProjectDirs::from("org", "fibonacci", "fibonacci_sequence")
+                .map(|dirs| dirs.cache_dir().to_path_buf()),
+        }
+    }
+
+    /// Open the default directory with the default size limit
+    pub fn open_default() -> io::Result<Self> {
+        let dir = Self::default_dir()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no cache directory on this platform"))?;
+        Self::open(dir, DEFAULT_MAX_BYTES)
+    }
+
+    /// Directory holding the entries
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Size the entries are kept under
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    fn path(&self, key: &Key) -> PathBuf {
+        self.dir.join(key.file_name())
+    }
+
+    /// Read a value, or `None` if it is missing or unusable
+    ///
+    /// Unusable entries are deleted so the next `insert` replaces them.
+    pub fn get(&self, key: &Key) -> Option<BigUint> {
+        let path = self.path(key);
+        match read_entry(&path, key) {
+            Ok(value) => {
+                // Reading counts as use for eviction; failing to record it is harmless
+                let _ = File::options()
+                    .write(true)
+                    .open(&path)
+                    .and_then(|file| file.set_modified(SystemTime::now()));
+                debug!(%key, "result cache hit");
+                Some(value)
+            }
+            Err(EntryError::Unreadable(_)) => {
+                debug!(%key, "result cache miss");
+                None
+            }
+            Err(error) => {
+                warn!(%key, ?error, "deleting unusable result cache entry");
+                let _ = fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    /// Store a value, then evict the least recently used entries over the limit
+    ///
+    /// A value larger than the whole limit is not stored.
+    pub fn insert(&self, key: &Key, value: &BigUint) -> io::Result<()> {
+        let contents = encode(key, value);
+        if contents.len() as u64 > self.max_bytes {
+            return Ok(());
+        }
+        // Write then rename, so readers never see half an entry
+        let path = self.path(key);
+        let temporary = self.dir.join(format!(".{}.{}.tmp", key.file_name(), std::process::id()));
+        fs::write(&temporary, contents)?;
+        fs::rename(&temporary, &path)?;
+        debug!(%key, "stored result");
+        self.evict(self.max_bytes, Some(key))?;
+        Ok(())
+    }
+
+    /// Read a value, or compute and store it
+    ///
+    /// The cache is best effort: if the entry cannot be written the computed
+    /// value is still returned.
+    pub fn get_or_insert_with(&self, key: &Key, compute: impl FnOnce() -> BigUint) -> BigUint {
+        if let Some(value) = self.get(key) {
+            return value;
+        }
+        let value = compute();
+        if let Err(error) = self.insert(key, &value) {
+            warn!(%key, %error, "could not store result");
+        }
+        value
+    }
+
+    /// Every entry, ordered by sequence, modulus and n
+    pub fn entries(&self) -> io::Result<Vec<EntryInfo>> {
+        let mut entries = Vec::new();
+        for file in fs::read_dir(&self.dir)? {
+            let file = file?;
+            let Some(key) = file.file_name().to_str().and_then(Key::from_file_name) else {
+                continue;
+            };
+            let metadata = file.metadata()?;
+            if metadata.is_file() {
+                entries.push(EntryInfo {
+                    key,
+                    path: file.path(),
+                    bytes: metadata.len(),
+                    used: metadata.modified()?,
+                });
+            }
+        }
+        entries.sort_by_key(|entry| entry.key);
+        Ok(entries)
+    }
+
+    /// Check the version and checksum of every entry
+    pub fn verify(&self) -> io::Result<Vec<(EntryInfo, Result<(), EntryError>)>> {
+        Ok(self
+            .entries()?
+            .into_iter()
+            .map(|entry| {
+                let status = read_entry(&entry.path, &entry.key).map(drop);
+                (entry, status)
+            })
+            .collect())
+    }
+
+    /// Delete unusable entries, then the least recently used ones until the
+    /// rest fit in `max_bytes`
+    pub fn prune(&self, max_bytes: u64) -> io::Result<PruneReport> {
+        let mut report = PruneReport::default();
+        for (entry, status) in self.verify()? {
+            if status.is_err() {
+                fs::remove_file(&entry.path)?;
+                report.removed += 1;
+                report.freed += entry.bytes;
+            }
+        }
+        let evicted = self.evict(max_bytes, None)?;
+        report.removed += evicted.removed;
+        report.freed += evicted.freed;
+        Ok(report)
+    }
+
+    fn evict(&self, max_bytes: u64, keep: Option<&Key>) -> io::Result<PruneReport> {
+        let mut entries = self.entries()?;
+        let mut total: u64 = entries.iter().map(|entry| entry.bytes).sum();
+        entries.sort_by_key(|entry| entry.used);
+
+        let mut report = PruneReport::default();
+        for entry in entries {
+            if total <= max_bytes {
+                break;
+            }
+            if Some(&entry.key) == keep {
+                continue;
+            }
+            fs::remove_file(&entry.path)?;
+            total -= entry.bytes;
+            report.removed += 1;
+            report.freed += entry.bytes;
+        }
+        if report.removed > 0 {
+            info!(removed = report.removed, freed = report.freed, "evicted result cache entries");
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fibonacci;
+    use std::time::Duration;
+
+    /// An empty cache directory unique to one test
+    fn temp_cache(name: &str, max_bytes: u64) -> DiskCache {
+        let dir = std::env::temp_dir().join(format!("fibonacci_store_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        DiskCache::open(dir, max_bytes).unwrap()
+    }
+
+    fn set_used(cache: &DiskCache, key: &Key, seconds: u64) {
+        let file = File::options().write(true).open(cache.path(key)).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds)).unwrap();
+    }
+
+    #[test]
+    fn test_key_file_names() {
+        let key = Key::new(Sequence::Fibonacci, 100_000);
+        assert_eq!(key.file_name(), "fibonacci-100000.fib");
+        assert_eq!(key.to_string(), "F(100000)");
+        let key = Key::modular(Sequence::Lucas, 5, 7);
+        assert_eq!(key.file_name(), "lucas-5-mod-7.fib");
+        assert_eq!(key.to_string(), "L(5) mod 7");
+        assert_eq!(Key::from_file_name("lucas-5-mod-7.fib"), Some(key));
+        assert_eq!(Key::from_file_name("fibonacci-05.fib"), None);
+        assert_eq!(Key::from_file_name("fibonacci-5.txt"), None);
+        assert_eq!(Key::from_file_name(".fibonacci-5.fib.42.tmp"), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let cache = temp_cache("round_trip", DEFAULT_MAX_BYTES);
+        let key = Key::new(Sequence::Fibonacci, 1_000);
+        assert_eq!(cache.get(&key), None);
+
+        let value = cache.get_or_insert_with(&key, || fibonacci::fib_big(1_000));
+        assert_eq!(cache.get(&key), Some(value.clone()));
+        assert_eq!(cache.get_or_insert_with(&key, || unreachable!()), value);
+
+        let entries = cache.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, key);
+        fs::remove_dir_all(cache.dir()).unwrap();
+    }
+
+    #[test]
+    fn test_damaged_entries() {
+        let cache = temp_cache("damaged", DEFAULT_MAX_BYTES);
+        let (a, b, c) = (
+            Key::new(Sequence::Fibonacci, 10),
+            Key::new(Sequence::Fibonacci, 11),
+            Key::new(Sequence::Lucas, 10),
+        );
+        for key in [&a, &b, &c] {
+            cache.insert(key, &BigUint::from(key.n)).unwrap();
+        }
+        let contents = fs::read_to_string(cache.path(&a)).unwrap();
+        fs::write(cache.path(&a), contents.replace("\n10\n", "\n11\n")).unwrap();
+        let contents = fs::read_to_string(cache.path(&b)).unwrap();
+        fs::write(cache.path(&b), contents.replacen("fibonacci-cache 1", "fibonacci-cache 0", 1)).unwrap();
+
+        let statuses: Vec<_> = cache.verify().unwrap().into_iter().map(|(entry, status)| (entry.key, status)).collect();
+        assert_eq!(
+            statuses,
+            vec![(a, Err(EntryError::Checksum)), (b, Err(EntryError::Version { found: 0 })), (c, Ok(()))]
+        );
+
+        assert_eq!(cache.prune(DEFAULT_MAX_BYTES).unwrap().removed, 2);
+        assert_eq!(cache.entries().unwrap().len(), 1);
+        // A bad entry is a miss and is replaced on the next insert
+        fs::write(cache.path(&c), "garbage").unwrap();
+        assert_eq!(cache.get(&c), None);
+        assert!(cache.entries().unwrap().is_empty());
+        fs::remove_dir_all(cache.dir()).unwrap();
+    }
+
+    #[test]
+    fn test_size_limit() {
+        let value = BigUint::from(12345u32);
+        let entry_bytes = encode(&Key::new(Sequence::Fibonacci, 1), &value).len() as u64;
+        let cache = temp_cache("size_limit", entry_bytes * 2 + 1);
+
+        let keys: Vec<Key> = (1..=3).map(|n| Key::new(Sequence::Fibonacci, n)).collect();
+        cache.insert(&keys[0], &value).unwrap();
+        cache.insert(&keys[1], &value).unwrap();
+        set_used(&cache, &keys[0], 2_000);
+        set_used(&cache, &keys[1], 1_000);
+        // keys[1] was used longest ago, so it goes first
+        cache.insert(&keys[2], &value).unwrap();
+        let kept: Vec<Key> = cache.entries().unwrap().into_iter().map(|entry| entry.key).collect();
+        assert_eq!(kept, vec![keys[0], keys[2]]);
+
+        assert_eq!(cache.prune(0).unwrap(), PruneReport { removed: 2, freed: entry_bytes * 2 });
+        assert!(cache.entries().unwrap().is_empty());
+
+        let tiny = DiskCache::open(cache.dir(), 10).unwrap();
+        tiny.insert(&keys[0], &value).unwrap();
+        assert!(tiny.entries().unwrap().is_empty());
+        fs::remove_dir_all(cache.dir()).unwrap();
+    }
+}
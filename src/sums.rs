@@ -0,0 +1,455 @@
+#[cfg(feature = "bigint")]
+use num_bigint::{BigInt, BigUint};
+
+use crate::fibonacci;
+
+// All aggregates over F(from..=to) reduce to a few Fibonacci numbers near the
+// range ends, so they run in O(log n):
+//
+//   Σ F(i)            = F(to+2) - F(from+1)
+//   Σ F(i)²           = F(to)F(to+1) - F(from-1)F(from)
+//   Σ F(i), i ≡ p (2) = F(t+1) - F(s-1)      (s..=t: first/last index with parity p)
+//   Σ (-1)^i F(i)     = A(to) - A(from-1),   A(n) = (-1)^n F(n-1) - 1
+//   Σ i·F(i)          = W(to) - W(from-1),   W(n) = n F(n+2) - F(n+3) + 2
+//
+// with F(-1) = 1 and A(-1) = W(-1) = 0.
+
+/// Arithmetic used to evaluate the closed forms
+trait Arithmetic {
+    type Value: Clone;
+
+    /// F(n) for n >= 0, or `None` if it cannot be represented
+    fn fib(&self, n: u64) -> Option<Self::Value>;
+    fn constant(&self, x: u64) -> Self::Value;
+    fn add(&self, a: Self::Value, b: Self::Value) -> Option<Self::Value>;
+    fn sub(&self, a: Self::Value, b: Self::Value) -> Option<Self::Value>;
+    fn mul(&self, a: Self::Value, b: Self::Value) -> Option<Self::Value>;
+    fn neg(&self, a: Self::Value) -> Option<Self::Value> {
+        self.sub(self.constant(0), a)
+    }
+}
+
+/// Exact signed arithmetic that reports overflow
+struct Checked;
+
+impl Arithmetic for Checked {
+    type Value = i128;
+
+    fn fib(&self, n: u64) -> Option<i128> {
+        fibonacci::fib_u128(u32::try_from(n).ok()?).and_then(|f| i128::try_from(f).ok())
+    }
+    fn constant(&self, x: u64) -> i128 {
+        x as i128
+    }
+    fn add(&self, a: i128, b: i128) -> Option<i128> {
+        a.checked_add(b)
+    }
+    fn sub(&self, a: i128, b: i128) -> Option<i128> {
+        a.checked_sub(b)
+    }
+    fn mul(&self, a: i128, b: i128) -> Option<i128> {
+        a.checked_mul(b)
+    }
+}
+
+/// Residues modulo a non-zero modulus
+struct Modular(u64);
+
+impl Arithmetic for Modular {
+    type Value = u64;
+
+    fn fib(&self, n: u64) -> Option<u64> {
+        fibonacci::fib_mod(n, self.0)
+    }
+    fn constant(&self, x: u64) -> u64 {
+        x % self.0
+    }
+    fn add(&self, a: u64, b: u64) -> Option<u64> {
+        Some(((a as u128 + b as u128) % self.0 as u128) as u64)
+    }
+    fn sub(&self, a: u64, b: u64) -> Option<u64> {
+        Some(((a as u128 + self.0 as u128 - b as u128) % self.0 as u128) as u64)
+    }
+    fn mul(&self, a: u64, b: u64) -> Option<u64> {
+        Some((a as u128 * b as u128 % self.0 as u128) as u64)
+    }
+}
+
+/// Arbitrary-precision signed arithmetic
+#[cfg(feature = "bigint")]
+struct Big;
+
+#[cfg(feature = "bigint")]
+impl Arithmetic for Big {
+    type Value = BigInt;
+
+    fn fib(&self, n: u64) -> Option<BigInt> {
+        Some(BigInt::from(fibonacci::fib_big(n)))
+    }
+    fn constant(&self, x: u64) -> BigInt {
+        BigInt::from(x)
+    }
+    fn add(&self, a: BigInt, b: BigInt) -> Option<BigInt> {
+        Some(a + b)
+    }
+    fn sub(&self, a: BigInt, b: BigInt) -> Option<BigInt> {
+        Some(a - b)
+    }
+    fn mul(&self, a: BigInt, b: BigInt) -> Option<BigInt> {
+        Some(a * b)
+    }
+}
+
+/// F(n) for n >= -1
+///
+/// The closed forms reach a few indices past `to`, so n may exceed
+/// `u64::MAX` by up to 3; such terms are built from the two before them.
+fn fib_at<A: Arithmetic>(a: &A, n: i128) -> Option<A::Value> {
+    match (n, u64::try_from(n)) {
+        (-1, _) => Some(a.constant(1)),
+        (_, Ok(n)) => a.fib(n),
+        (_, Err(_)) => a.add(fib_at(a, n - 1)?, fib_at(a, n - 2)?),
+    }
+}
+
+fn range_sum<A: Arithmetic>(a: &A, from: u64, to: u64) -> Option<A::Value> {
+    if from > to {
+        return Some(a.constant(0));
+    }
+    a.sub(fib_at(a, to as i128 + 2)?, fib_at(a, from as i128 + 1)?)
+}
+
+fn squares_sum<A: Arithmetic>(a: &A, from: u64, to: u64) -> Option<A::Value> {
+    if from > to {
+        return Some(a.constant(0));
+    }
+    let (to, from) = (to as i128, from as i128);
+    let upper = a.mul(fib_at(a, to)?, fib_at(a, to + 1)?)?;
+    let lower = a.mul(fib_at(a, from - 1)?, fib_at(a, from)?)?;
+    a.sub(upper, lower)
+}
+
+fn parity_sum<A: Arithmetic>(a: &A, from: u64, to: u64, parity: u64) -> Option<A::Value> {
+    let first = from as i128 + ((from % 2 != parity) as i128);
+    let last = to as i128 - ((to % 2 != parity) as i128);
+    if first > last {
+        return Some(a.constant(0));
+    }
+    a.sub(fib_at(a, last + 1)?, fib_at(a, first - 1)?)
+}
+
+/// A(n) = Σ_{i=0}^{n} (-1)^i F(i)
+fn alternating_prefix<A: Arithmetic>(a: &A, n: i128) -> Option<A::Value> {
+    if n < 0 {
+        return Some(a.constant(0));
+    }
+    let term = fib_at(a, n - 1)?;
+    let signed = if n % 2 == 0 { term } else { a.neg(term)? };
+    a.sub(signed, a.constant(1))
+}
+
+fn alternating_sum<A: Arithmetic>(a: &A, from: u64, to: u64) -> Option<A::Value> {
+    if from > to {
+        return Some(a.constant(0));
+    }
+    a.sub(
+        alternating_prefix(a, to as i128)?,
+        alternating_prefix(a, from as i128 - 1)?,
+    )
+}
+
+/// W(n) = Σ_{i=0}^{n} i·F(i)
+fn weighted_prefix<A: Arithmetic>(a: &A, n: i128) -> Option<A::Value> {
+    if n < 0 {
+        return Some(a.constant(0));
+    }
+    let scaled = a.mul(a.constant(n as u64), fib_at(a, n + 2)?)?;
+    a.add(a.sub(scaled, fib_at(a, n + 3)?)?, a.constant(2))
+}
+
+fn weighted_sum<A: Arithmetic>(a: &A, from: u64, to: u64) -> Option<A::Value> {
+    if from > to {
+        return Some(a.constant(0));
+    }
+    a.sub(
+        weighted_prefix(a, to as i128)?,
+        weighted_prefix(a, from as i128 - 1)?,
+    )
+}
+
+fn to_u64(value: Option<i128>) -> Option<u64> {
+    value.and_then(|v| u64::try_from(v).ok())
+}
+
+/// Big-integer arithmetic never fails, and these sums are never negative
+#[cfg(feature = "bigint")]
+fn to_biguint(value: Option<BigInt>) -> BigUint {
+    value
+        .and_then(|v| v.to_biguint())
+        .expect("sums of Fibonacci numbers are non-negative")
+}
+
+/// Σ F(i) for i in `from..=to`, or `None` if it does not fit in a u64
+///
+/// # Examples
+/// ```
+/// use fibonacci_sequence::sums::sum_range_checked;
+///
+/// // 0 + 1 + 1 + 2 + 3 + 5 + 8 + 13 + 21 + 34 + 55
+/// assert_eq!(sum_range_checked(0, 10), Some(143));
+/// ```
+pub fn sum_range_checked(from: u32, to: u32) -> Option<u64> {
+    to_u64(range_sum(&Checked, from as u64, to as u64))
+}
+
+/// Σ F(i) for i in `from..=to`, modulo `m` (`None` if `m` is zero)
+pub fn sum_range_mod(from: u64, to: u64, m: u64) -> Option<u64> {
+    (m != 0).then(|| range_sum(&Modular(m), from, to))?
+}
+
+/// Σ F(i) for i in `from..=to` as an arbitrary-precision integer
+///
+/// # Panics
+/// The result has about 0.69·`to` bits, so for `to` in the trillions and
+/// beyond the allocation fails and the process aborts
+#[cfg(feature = "bigint")]
+pub fn sum_range_big(from: u64, to: u64) -> BigUint {
+    to_biguint(range_sum(&Big, from, to))
+}
+
+/// Σ F(i)² for i in `from..=to`, or `None` if it does not fit in a u64
+pub fn sum_squares_checked(from: u32, to: u32) -> Option<u64> {
+    to_u64(squares_sum(&Checked, from as u64, to as u64))
+}
+
+/// Σ F(i)² for i in `from..=to`, modulo `m` (`None` if `m` is zero)
+pub fn sum_squares_mod(from: u64, to: u64, m: u64) -> Option<u64> {
+    (m != 0).then(|| squares_sum(&Modular(m), from, to))?
+}
+
+/// Σ F(i)² for i in `from..=to` as an arbitrary-precision integer
+///
+/// # Panics
+/// As [`sum_range_big`], when `to` is too large for F(to) to fit in memory
+#[cfg(feature = "bigint")]
+pub fn sum_squares_big(from: u64, to: u64) -> BigUint {
+    to_biguint(squares_sum(&Big, from, to))
+}
+
+/// Σ F(i) over even indices i in `from..=to`, or `None` if it does not fit in a u64
+///
+/// This selects terms by index (F(0), F(2), F(4), ...), not by value: F(i)
+/// is even exactly when i is a multiple of 3.
+pub fn sum_even_index_checked(from: u32, to: u32) -> Option<u64> {
+    to_u64(parity_sum(&Checked, from as u64, to as u64, 0))
+}
+
+/// Σ F(i) over even indices i in `from..=to`, modulo `m` (`None` if `m` is zero)
+pub fn sum_even_index_mod(from: u64, to: u64, m: u64) -> Option<u64> {
+    (m != 0).then(|| parity_sum(&Modular(m), from, to, 0))?
+}
+
+/// Σ F(i) over even indices i in `from..=to` as an arbitrary-precision integer
+///
+/// # Panics
+/// As [`sum_range_big`], when `to` is too large for F(to) to fit in memory
+#[cfg(feature = "bigint")]
+pub fn sum_even_index_big(from: u64, to: u64) -> BigUint {
+    to_biguint(parity_sum(&Big, from, to, 0))
+}
+
+/// Σ F(i) over odd indices i in `from..=to`, or `None` if it does not fit in a u64
+///
+/// Like [`sum_even_index_checked`], this selects terms by index, not by value.
+pub fn sum_odd_index_checked(from: u32, to: u32) -> Option<u64> {
+    to_u64(parity_sum(&Checked, from as u64, to as u64, 1))
+}
+
+/// Σ F(i) over odd indices i in `from..=to`, modulo `m` (`None` if `m` is zero)
+pub fn sum_odd_index_mod(from: u64, to: u64, m: u64) -> Option<u64> {
+    (m != 0).then(|| parity_sum(&Modular(m), from, to, 1))?
+}
+
+/// Σ F(i) over odd indices i in `from..=to` as an arbitrary-precision integer
+///
+/// # Panics
+/// As [`sum_range_big`], when `to` is too large for F(to) to fit in memory
+#[cfg(feature = "bigint")]
+pub fn sum_odd_index_big(from: u64, to: u64) -> BigUint {
+    to_biguint(parity_sum(&Big, from, to, 1))
+}
+
+/// Σ (-1)^i F(i) for i in `from..=to`, or `None` if it does not fit in an i64
+pub fn alternating_sum_checked(from: u32, to: u32) -> Option<i64> {
+    alternating_sum(&Checked, from as u64, to as u64).and_then(|v| i64::try_from(v).ok())
+}
+
+/// Σ (-1)^i F(i) for i in `from..=to`, as a residue in `0..m` (`None` if `m` is zero)
+pub fn alternating_sum_mod(from: u64, to: u64, m: u64) -> Option<u64> {
+    (m != 0).then(|| alternating_sum(&Modular(m), from, to))?
+}
+
+/// Σ (-1)^i F(i) for i in `from..=to` as an arbitrary-precision integer
+///
+/// # Panics
+/// As [`sum_range_big`], when `to` is too large for F(to) to fit in memory
+#[cfg(feature = "bigint")]
+pub fn alternating_sum_big(from: u64, to: u64) -> BigInt {
+    alternating_sum(&Big, from, to).expect("big-integer arithmetic never fails")
+}
+
+/// Σ i·F(i) for i in `from..=to`, or `None` if it does not fit in a u64
+pub fn weighted_sum_checked(from: u32, to: u32) -> Option<u64> {
+    to_u64(weighted_sum(&Checked, from as u64, to as u64))
+}
+
+/// Σ i·F(i) for i in `from..=to`, modulo `m` (`None` if `m` is zero)
+pub fn weighted_sum_mod(from: u64, to: u64, m: u64) -> Option<u64> {
+    (m != 0).then(|| weighted_sum(&Modular(m), from, to))?
+}
+
+/// Σ i·F(i) for i in `from..=to` as an arbitrary-precision integer
+///
+/// # Panics
+/// As [`sum_range_big`], when `to` is too large for F(to) to fit in memory
+#[cfg(feature = "bigint")]
+pub fn weighted_sum_big(from: u64, to: u64) -> BigUint {
+    to_biguint(weighted_sum(&Big, from, to))
+}
+
+#[cfg(all(test, feature = "bigint"))]
+mod tests {
+    use super::*;
+    use crate::fibonacci::generate_sequence_iterative;
+    use num_traits::One;
+
+    /// Brute-force reference: Σ weight(i) * F(i) over `from..=to` in i128
+    fn brute(from: u32, to: u32, weight: impl Fn(u32, i128) -> i128) -> i128 {
+        (from..=to)
+            .map(|i| weight(i, fibonacci::fib_u128(i).unwrap() as i128))
+            .sum()
+    }
+
+    fn ranges() -> impl Iterator<Item = (u32, u32)> {
+        (0..40).flat_map(|from| (from..40).map(move |to| (from, to)))
+    }
+
+    #[test]
+    fn test_sum_range() {
+        let seq = generate_sequence_iterative(20);
+        assert_eq!(sum_range_checked(0, 20), Some(seq.iter().sum()));
+        assert_eq!(sum_range_checked(5, 4), Some(0));
+        for (from, to) in ranges() {
+            let expected = brute(from, to, |_, f| f);
+            assert_eq!(sum_range_checked(from, to), Some(expected as u64));
+            assert_eq!(sum_range_big(from as u64, to as u64), BigUint::from(expected as u64));
+            assert_eq!(sum_range_mod(from as u64, to as u64, 97), Some((expected % 97) as u64));
+        }
+    }
+
+    #[test]
+    fn test_sum_squares() {
+        for (from, to) in ranges() {
+            let expected = brute(from, to, |_, f| f * f);
+            assert_eq!(sum_squares_checked(from, to), Some(expected as u64));
+            assert_eq!(sum_squares_big(from as u64, to as u64), BigUint::from(expected as u64));
+            assert_eq!(sum_squares_mod(from as u64, to as u64, 1009), Some((expected % 1009) as u64));
+        }
+    }
+
+    #[test]
+    fn test_parity_sums() {
+        for (from, to) in ranges() {
+            let even = brute(from, to, |i, f| if i % 2 == 0 { f } else { 0 });
+            let odd = brute(from, to, |i, f| if i % 2 == 1 { f } else { 0 });
+            assert_eq!(sum_even_index_checked(from, to), Some(even as u64), "{}..={}", from, to);
+            assert_eq!(sum_odd_index_checked(from, to), Some(odd as u64), "{}..={}", from, to);
+            assert_eq!(sum_even_index_big(from as u64, to as u64), BigUint::from(even as u64));
+            assert_eq!(sum_odd_index_mod(from as u64, to as u64, 10), Some((odd % 10) as u64));
+        }
+    }
+
+    #[test]
+    fn test_alternating_sum() {
+        for (from, to) in ranges() {
+            let expected = brute(from, to, |i, f| if i % 2 == 0 { f } else { -f });
+            assert_eq!(alternating_sum_checked(from, to), Some(expected as i64));
+            assert_eq!(alternating_sum_big(from as u64, to as u64), BigInt::from(expected));
+            assert_eq!(
+                alternating_sum_mod(from as u64, to as u64, 13),
+                Some(expected.rem_euclid(13) as u64)
+            );
+        }
+    }
+
+    #[test]
+    fn test_weighted_sum() {
+        for (from, to) in ranges() {
+            let expected = brute(from, to, |i, f| i as i128 * f);
+            assert_eq!(weighted_sum_checked(from, to), Some(expected as u64));
+            assert_eq!(weighted_sum_big(from as u64, to as u64), BigUint::from(expected as u64));
+            assert_eq!(weighted_sum_mod(from as u64, to as u64, 1000), Some((expected % 1000) as u64));
+        }
+    }
+
+    #[test]
+    fn test_checked_overflow() {
+        // F(0) + ... + F(n) = F(n+2) - 1 fits in a u64 up to n = 91
+        assert_eq!(sum_range_checked(0, 91), Some(fibonacci::fib_checked(93).unwrap() - 1));
+        assert_eq!(sum_range_checked(0, 92), None);
+        assert_eq!(sum_squares_checked(0, 1000), None);
+        assert_eq!(weighted_sum_checked(0, u32::MAX), None);
+        assert_eq!(alternating_sum_checked(0, u32::MAX), None);
+    }
+
+    #[test]
+    fn test_mod_near_u64_max() {
+        // F(n) mod 3 repeats every 8 terms (the Pisano period), so the
+        // closed forms can be checked at indices past u64::MAX
+        let fib3 = |n: u128| fibonacci::fib_mod((n % 8) as u64, 3).unwrap();
+        let max = u64::MAX as u128;
+        assert_eq!(sum_range_mod(0, u64::MAX, 3), Some((fib3(max + 2) + 3 - 1) % 3));
+        assert_eq!(sum_range_mod(u64::MAX, u64::MAX, 3), Some(fib3(max)));
+        assert_eq!(sum_odd_index_mod(u64::MAX, u64::MAX, 3), Some(fib3(max)));
+        assert_eq!(sum_even_index_mod(u64::MAX - 1, u64::MAX, 3), Some(fib3(max - 1)));
+        assert_eq!(sum_squares_mod(u64::MAX, u64::MAX, 3), Some(fib3(max) * fib3(max) % 3));
+        assert_eq!(weighted_sum_mod(u64::MAX, u64::MAX, 3), Some((max % 3) as u64 * fib3(max) % 3));
+        assert!(alternating_sum_mod(0, u64::MAX, 1_000_000_007).is_some());
+    }
+
+    #[test]
+    fn test_mod_and_big_agree_for_large_n() {
+        let m = 1_000_000_007;
+        for (from, to) in [(0, 500), (123, 456), (300, 300), (1, 1000)] {
+            let modulus = BigUint::from(m);
+            let check = |big: BigUint, modular: Option<u64>| {
+                assert_eq!(modular, Some(u64::try_from(big % &modulus).unwrap()));
+            };
+            check(sum_range_big(from, to), sum_range_mod(from, to, m));
+            check(sum_squares_big(from, to), sum_squares_mod(from, to, m));
+            check(sum_even_index_big(from, to), sum_even_index_mod(from, to, m));
+            check(sum_odd_index_big(from, to), sum_odd_index_mod(from, to, m));
+            check(weighted_sum_big(from, to), weighted_sum_mod(from, to, m));
+
+            let alternating = alternating_sum_big(from, to) % BigInt::from(m);
+            let alternating = (alternating + BigInt::from(m)) % BigInt::from(m);
+            assert_eq!(alternating_sum_mod(from, to, m), Some(u64::try_from(alternating).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_zero_modulus() {
+        assert_eq!(sum_range_mod(0, 10, 0), None);
+        assert_eq!(alternating_sum_mod(0, 10, 0), None);
+        assert_eq!(sum_range_mod(0, 10, 1), Some(0));
+    }
+
+    #[test]
+    fn test_huge_indices() {
+        // Closed forms make sums over 10^18 terms instant
+        let n = 1_000_000_000_000_000_000;
+        let f = fibonacci::fib_mod(n + 2, 10).unwrap();
+        assert_eq!(sum_range_mod(0, n, 10), Some((f + 9) % 10));
+        assert_eq!(sum_range_big(0, 10_000), fibonacci::fib_big(10_002) - BigUint::one());
+    }
+}
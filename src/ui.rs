@@ -1,8 +1,24 @@
+use std::path::Path;
+
 use eframe::egui::{self, Color32, Vec2};
-use crate::visualization::{SpiralDrawer, utils};
+use num_bigint::BigUint;
+use crate::export::{self, ExportFormat};
+use crate::fibonacci::Algorithm;
+use crate::history::{History, HistoryEntry};
+use crate::lattice;
+use crate::logging::{LogBuffer, Record};
+use crate::radix::{self, Base};
+use crate::settings::{self, Settings};
+use crate::sums;
+use crate::visualization::{SpiralDrawer, utils::{self, SequenceFormat}};
+
+pub use crate::validation::{self, MAX_FIBONACCI_N};
 
-/// Maximum supported Fibonacci number for optimal display
-pub const MAX_FIBONACCI_N: u32 = 25;
+/// Largest lattice index drawn, F(14) = 377 points
+const MAX_LATTICE_INDEX: u32 = 14;
+
+/// Most sunflower points drawn
+const MAX_SUNFLOWER_POINTS: usize = 1000;
 
 /// UI component for input controls
 pub struct InputControls;
@@ -12,10 +28,12 @@ impl InputControls {
     pub fn render(
         ui: &mut egui::Ui,
         input_text: &mut String,
+        max_n: u32,
+        algorithm: &mut Algorithm,
         on_calculate: &mut bool,
     ) {
         ui.horizontal(|ui| {
-            ui.label("Enter a number (0-25):");
+            ui.label(format!("Enter a number (0-{}):", max_n));
             ui.add_space(10.0);
 
             let response = ui.add(
@@ -28,6 +46,16 @@ impl InputControls {
 
             let calculate_button = ui.add_sized([150.0, 30.0], egui::Button::new("🌀 Generate Spiral"));
 
+            egui::ComboBox::from_id_salt("algorithm")
+                .selected_text(algorithm.name())
+                .show_ui(ui, |ui| {
+                    for option in Algorithm::ALL {
+                        ui.selectable_value(algorithm, option, option.name());
+                    }
+                })
+                .response
+                .on_hover_text("How F(n) is calculated");
+
             // Handle button click or Enter key
             if calculate_button.clicked()
                 || (response.has_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)))
@@ -38,6 +66,23 @@ impl InputControls {
     }
 }
 
+/// UI component for a calculation in progress
+pub struct ProgressControls;
+
+impl ProgressControls {
+    /// Render a progress bar with a Cancel button
+    ///
+    /// # Returns
+    /// Whether Cancel was clicked
+    pub fn render(ui: &mut egui::Ui, fraction: f32) -> bool {
+        ui.horizontal(|ui| {
+            ui.add(egui::ProgressBar::new(fraction).desired_width(250.0).show_percentage());
+            ui.button("✖ Cancel").clicked()
+        })
+        .inner
+    }
+}
+
 /// UI component for displaying results
 pub struct ResultDisplay;
 
@@ -54,13 +99,35 @@ impl ResultDisplay {
         }
     }
 
-    /// Render the Fibonacci sequence display
-    pub fn render_sequence(ui: &mut egui::Ui, fibonacci_sequence: &[u64]) {
+    /// Render F(n) with a selector for the number base
+    pub fn render_in_base(ui: &mut egui::Ui, current_n: u32, value: u64, base: &mut Base) {
+        ui.horizontal(|ui| {
+            ui.label("Show F(n) in base:");
+            for option in Base::ALL {
+                ui.selectable_value(base, option, option.label());
+            }
+        });
+
+        let digits = radix::format_in_base(&BigUint::from(value), *base);
+        ui.label(
+            egui::RichText::new(format!("F({}) = {}", current_n, digits))
+                .font(egui::FontId::monospace(13.0)),
+        );
+        ui.add_space(10.0);
+    }
+
+    /// Render the Fibonacci sequence display with a selector for the layout
+    pub fn render_sequence(ui: &mut egui::Ui, fibonacci_sequence: &[u64], format: &mut SequenceFormat) {
         ui.group(|ui| {
-            ui.label("Sequence:");
+            ui.horizontal(|ui| {
+                ui.label("Sequence:");
+                for option in SequenceFormat::ALL {
+                    ui.selectable_value(format, option, option.label());
+                }
+            });
             ui.add_space(5.0);
 
-            let sequence_text = utils::format_sequence(fibonacci_sequence);
+            let sequence_text = format.format(fibonacci_sequence);
 
             ui.label(
                 egui::RichText::new(&sequence_text)
@@ -70,28 +137,494 @@ impl ResultDisplay {
     }
 }
 
-/// UI component for the Fibonacci spiral visualization
-pub struct SpiralVisualization {
-    drawer: SpiralDrawer,
+/// "Export…" window for saving sequences to disk and loading them back
+pub struct ExportDialog {
+    /// Whether the window is shown
+    pub open: bool,
+    format: ExportFormat,
+    path: String,
+    status: Option<Result<String, String>>,
 }
 
-impl Default for SpiralVisualization {
+impl Default for ExportDialog {
     fn default() -> Self {
         Self {
-            drawer: SpiralDrawer::default(),
+            open: false,
+            format: ExportFormat::default(),
+            path: "fibonacci_sequence.csv".to_string(),
+            status: None,
         }
     }
 }
 
+impl ExportDialog {
+    /// Show a success or error message under the buttons
+    pub fn set_status(&mut self, status: Result<String, String>) {
+        self.status = Some(status);
+    }
+
+    /// Render the window if it is open
+    ///
+    /// # Returns
+    /// The sequence read from disk when the user clicked Load
+    pub fn render(&mut self, ctx: &egui::Context, fibonacci_sequence: &[u64]) -> Option<Vec<u64>> {
+        let mut imported = None;
+        let mut open = self.open;
+
+        egui::Window::new("Export sequence")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Format:");
+                    for option in ExportFormat::ALL {
+                        if ui.selectable_value(&mut self.format, option, option.label()).changed() {
+                            self.path = Path::new(&self.path)
+                                .with_extension(option.extension())
+                                .display()
+                                .to_string();
+                        }
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("File:");
+                    ui.add(egui::TextEdit::singleline(&mut self.path).desired_width(260.0));
+                });
+                if cfg!(target_arch = "wasm32") {
+                    ui.small("Files are kept in this browser's local storage");
+                }
+                ui.small("Columns: index, value, ratio, digits, parity");
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    let can_save = !fibonacci_sequence.is_empty();
+                    if ui.add_enabled(can_save, egui::Button::new("💾 Save")).clicked() {
+                        let path = Path::new(&self.path);
+                        self.status = Some(
+                            export::export_to_file(path, fibonacci_sequence)
+                                .map(|()| format!("Saved {} terms to {}", fibonacci_sequence.len(), path.display())),
+                        );
+                    }
+
+                    if ui.button("📂 Load").clicked() {
+                        match export::import_from_file(Path::new(&self.path)) {
+                            Ok(sequence) => imported = Some(sequence),
+                            Err(message) => self.status = Some(Err(message)),
+                        }
+                    }
+                });
+
+                match &self.status {
+                    Some(Ok(message)) => {
+                        ui.colored_label(Color32::DARK_GREEN, message);
+                    }
+                    Some(Err(message)) => {
+                        ui.colored_label(Color32::DARK_RED, message);
+                    }
+                    None => {}
+                }
+            });
+
+        self.open = open;
+        imported
+    }
+}
+
+/// Side panel listing past calculations, newest first
+pub struct HistoryPanel {
+    /// Whether the panel is shown
+    pub open: bool,
+    path: String,
+    status: Option<Result<String, String>>,
+}
+
+impl Default for HistoryPanel {
+    fn default() -> Self {
+        Self {
+            open: false,
+            path: "fibonacci_history.csv".to_string(),
+            status: None,
+        }
+    }
+}
+
+impl HistoryPanel {
+    /// Render the panel; pinning and deleting change `history` directly
+    ///
+    /// # Returns
+    /// The entry the user clicked, to show again
+    pub fn render(&mut self, ctx: &egui::Context, history: &mut History) -> Option<HistoryEntry> {
+        let mut selected = None;
+        let mut toggled = None;
+        let mut removed = None;
+
+        egui::SidePanel::right("history_panel")
+            .resizable(true)
+            .default_width(240.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.heading("🕘 History");
+                    if ui.small_button("✖").on_hover_text("Close").clicked() {
+                        self.open = false;
+                    }
+                });
+                ui.small("Click an entry to show it again. Times are UTC.");
+                ui.separator();
+
+                egui::ScrollArea::vertical().max_height(ui.available_height() - 90.0).show(ui, |ui| {
+                    if history.is_empty() {
+                        ui.weak("No calculations yet");
+                    }
+                    // Pinned entries first, each group newest first
+                    let mut order: Vec<usize> = (0..history.len()).rev().collect();
+                    order.sort_by_key(|&index| !history.entries()[index].pinned);
+                    for index in order {
+                        let entry = &history.entries()[index];
+                        ui.horizontal(|ui| {
+                            let pin = if entry.pinned { "📌" } else { "📍" };
+                            let hover = if entry.pinned { "Unpin" } else { "Pin" };
+                            if ui.small_button(pin).on_hover_text(hover).clicked() {
+                                toggled = Some(index);
+                            }
+                            if ui.small_button("🗑").on_hover_text("Delete").clicked() {
+                                removed = Some(index);
+                            }
+                            let label = format!("{}\n{} · {}", entry.summary(), entry.time_label(), entry.algorithm.name());
+                            if ui.selectable_label(false, label).clicked() {
+                                selected = Some(*entry);
+                            }
+                        });
+                    }
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("File:");
+                    ui.add(egui::TextEdit::singleline(&mut self.path).desired_width(150.0));
+                });
+                ui.horizontal(|ui| {
+                    for format in [ExportFormat::Csv, ExportFormat::Json] {
+                        let button = egui::Button::new(format!("💾 {}", format.label()));
+                        if ui.add_enabled(!history.is_empty(), button).clicked() {
+                            self.path = Path::new(&self.path).with_extension(format.extension()).display().to_string();
+                            let path = Path::new(&self.path);
+                            self.status = Some(
+                                history
+                                    .export_to_file(path)
+                                    .map(|()| format!("Saved {} entries to {}", history.len(), path.display())),
+                            );
+                        }
+                    }
+                    if ui.button("Clear").on_hover_text("Delete every entry that is not pinned").clicked() {
+                        history.clear_unpinned();
+                    }
+                });
+
+                match &self.status {
+                    Some(Ok(message)) => {
+                        ui.colored_label(Color32::DARK_GREEN, message);
+                    }
+                    Some(Err(message)) => {
+                        ui.colored_label(Color32::DARK_RED, message);
+                    }
+                    None => {}
+                }
+            });
+
+        if let Some(index) = toggled {
+            history.toggle_pin(index);
+        }
+        if let Some(index) = removed {
+            history.remove(index);
+        }
+        selected
+    }
+}
+
+/// "Logs" window listing recent log records, newest last
+pub struct LogViewer {
+    /// Whether the window is shown
+    pub open: bool,
+    /// Least severe level shown
+    level: tracing::Level,
+    /// Only records containing this text are shown
+    search: String,
+}
+
+impl Default for LogViewer {
+    fn default() -> Self {
+        Self { open: false, level: tracing::Level::INFO, search: String::new() }
+    }
+}
+
+impl LogViewer {
+    const LEVELS: [tracing::Level; 5] = [
+        tracing::Level::ERROR,
+        tracing::Level::WARN,
+        tracing::Level::INFO,
+        tracing::Level::DEBUG,
+        tracing::Level::TRACE,
+    ];
+
+    /// Check if `record` passes the level and search filters
+    pub fn shows(&self, record: &Record) -> bool {
+        record.level <= self.level && record.to_string().contains(self.search.as_str())
+    }
+
+    /// Render the window if it is open
+    pub fn render(&mut self, ctx: &egui::Context, logs: &LogBuffer) {
+        let mut open = self.open;
+
+        egui::Window::new("Logs")
+            .open(&mut open)
+            .default_size([600.0, 300.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Level:");
+                    egui::ComboBox::from_id_salt("log_level")
+                        .selected_text(self.level.as_str())
+                        .show_ui(ui, |ui| {
+                            for level in Self::LEVELS {
+                                ui.selectable_value(&mut self.level, level, level.as_str());
+                            }
+                        });
+                    ui.label("Search:");
+                    ui.add(egui::TextEdit::singleline(&mut self.search).desired_width(150.0));
+                    if ui.button("🗑 Clear").clicked() {
+                        logs.clear();
+                    }
+                    if ui.button("📋 Copy").clicked() {
+                        let text: Vec<String> = logs
+                            .records()
+                            .iter()
+                            .filter(|record| self.shows(record))
+                            .map(Record::to_string)
+                            .collect();
+                        ui.ctx().copy_text(text.join("\n"));
+                    }
+                });
+                ui.small("Events below the FIBONACCI_LOG / --log filter are not recorded");
+                ui.separator();
+
+                egui::ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
+                    for record in logs.records().iter().filter(|record| self.shows(record)) {
+                        let color = match record.level {
+                            tracing::Level::ERROR => Color32::DARK_RED,
+                            tracing::Level::WARN => Color32::from_rgb(200, 120, 0),
+                            tracing::Level::INFO => ui.visuals().text_color(),
+                            _ => Color32::GRAY,
+                        };
+                        ui.monospace(egui::RichText::new(record.to_string()).color(color));
+                    }
+                });
+            });
+
+        self.open = open;
+    }
+}
+
+/// "Settings" window editing the settings file
+#[derive(Default)]
+pub struct SettingsDialog {
+    /// Whether the window is shown
+    pub open: bool,
+    draft: Settings,
+    status: Option<Result<String, String>>,
+}
+
+impl SettingsDialog {
+    /// Open the window on a copy of `settings`
+    pub fn show(&mut self, settings: &Settings) {
+        self.draft = settings.clone();
+        self.status = None;
+        self.open = true;
+    }
+
+    /// Render the window if it is open
+    ///
+    /// # Returns
+    /// The new settings once they have been written to `path`
+    pub fn render(&mut self, ctx: &egui::Context, path: Option<&Path>) -> Option<Settings> {
+        let mut saved = None;
+        let mut open = self.open;
+
+        egui::Window::new("Settings")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let draft = &mut self.draft;
+                egui::Grid::new("settings_grid").num_columns(2).spacing([20.0, 6.0]).show(ui, |ui| {
+                    ui.label("Algorithm:");
+                    egui::ComboBox::from_id_salt("settings_algorithm")
+                        .selected_text(draft.algorithm.name())
+                        .show_ui(ui, |ui| {
+                            for option in Algorithm::ALL {
+                                ui.selectable_value(&mut draft.algorithm, option, option.name());
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Largest n:");
+                    let max_n = draft.algorithm.max_n().min(validation::MAX_U64_N);
+                    draft.limits.max_n = draft.limits.max_n.min(max_n);
+                    ui.add(egui::DragValue::new(&mut draft.limits.max_n).range(1..=max_n));
+                    ui.end_row();
+
+                    ui.label("Grid size:");
+                    ui.add(egui::DragValue::new(&mut draft.display.grid_size).range(2.0..=100.0).speed(0.5));
+                    ui.end_row();
+
+                    ui.label("Colors:");
+                    ui.horizontal_wrapped(|ui| {
+                        let mut remove = None;
+                        for (i, color) in draft.display.colors.iter_mut().enumerate() {
+                            ui.color_edit_button_srgb(&mut color.0).context_menu(|ui| {
+                                if ui.button("Remove").clicked() {
+                                    remove = Some(i);
+                                    ui.close_menu();
+                                }
+                            });
+                        }
+                        if let Some(i) = remove.filter(|_| draft.display.colors.len() > 1) {
+                            draft.display.colors.remove(i);
+                        }
+                        let can_add = draft.display.colors.len() < settings::MAX_COLORS;
+                        if ui.add_enabled(can_add, egui::Button::new("➕")).clicked() {
+                            let last = draft.display.colors.last().copied();
+                            draft.display.colors.push(last.unwrap_or(settings::DEFAULT_COLORS[0]));
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Window size:");
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(&mut draft.window.width).range(300.0..=4000.0));
+                        ui.label("×");
+                        ui.add(egui::DragValue::new(&mut draft.window.height).range(200.0..=4000.0));
+                    });
+                    ui.end_row();
+                });
+                ui.small("Right-click a color to remove it. The window size applies on the next start.");
+                match path {
+                    Some(path) => ui.small(format!("File: {}", path.display())),
+                    None => ui.small("Settings cannot be saved on this platform"),
+                };
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    let save = ui.add_enabled(path.is_some(), egui::Button::new("💾 Save")).clicked();
+                    if let Some(path) = path.filter(|_| save) {
+                        self.status = Some(match self.draft.save(path) {
+                            Ok(()) => {
+                                saved = Some(self.draft.clone());
+                                Ok(format!("Saved to {}", path.display()))
+                            }
+                            Err(error) => Err(error.to_string()),
+                        });
+                    }
+                    if ui.button("↺ Defaults").clicked() {
+                        self.draft = Settings::default();
+                    }
+                });
+
+                match &self.status {
+                    Some(Ok(message)) => {
+                        ui.colored_label(Color32::DARK_GREEN, message);
+                    }
+                    Some(Err(message)) => {
+                        ui.colored_label(Color32::DARK_RED, message);
+                    }
+                    None => {}
+                }
+            });
+
+        self.open = open;
+        saved
+    }
+}
+
+/// What the visualization panel draws
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum VisualizationMode {
+    /// Golden spiral of Fibonacci rectangles
+    #[default]
+    Spiral,
+    /// Fibonacci lattice on the unit square
+    Lattice,
+    /// Vogel's sunflower (golden-angle points on a disk)
+    Sunflower,
+}
+
+/// Points drawn by a point-set view, kept until the view or its size changes
+struct PointSet {
+    mode: VisualizationMode,
+    /// k for the lattice, the point count for the sunflower
+    size: usize,
+    points: Vec<[f64; 2]>,
+    title: String,
+}
+
+/// UI component for the Fibonacci spiral visualization
+#[derive(Default)]
+pub struct SpiralVisualization {
+    drawer: SpiralDrawer,
+    mode: VisualizationMode,
+    point_set: Option<PointSet>,
+}
+
 impl SpiralVisualization {
     /// Create a new spiral visualization with custom drawer
     pub fn new(drawer: SpiralDrawer) -> Self {
-        Self { drawer }
+        Self {
+            drawer,
+            mode: VisualizationMode::default(),
+            point_set: None,
+        }
+    }
+
+    /// The drawer, to change its grid and colors
+    pub fn drawer_mut(&mut self) -> &mut SpiralDrawer {
+        &mut self.drawer
+    }
+
+    /// What is drawn
+    pub fn mode(&self) -> VisualizationMode {
+        self.mode
+    }
+
+    /// Change what is drawn
+    pub fn set_mode(&mut self, mode: VisualizationMode) {
+        self.mode = mode;
+    }
+
+    /// Generate the points for a point-set view, unless the view and the
+    /// number of points are the same as last frame
+    fn update_point_set(&mut self, mode: VisualizationMode, current_n: u32, last: u64) {
+        let size = match mode {
+            VisualizationMode::Lattice => current_n.min(MAX_LATTICE_INDEX) as usize,
+            _ => last.clamp(1, MAX_SUNFLOWER_POINTS as u64) as usize,
+        };
+        if self.point_set.as_ref().is_some_and(|set| set.mode == mode && set.size == size) {
+            return;
+        }
+        let (points, title) = match mode {
+            VisualizationMode::Lattice => {
+                // MAX_LATTICE_INDEX keeps F(k) well under MAX_LATTICE_POINTS
+                let points = lattice::fibonacci_lattice(size as u32).unwrap_or_default();
+                let title = format!("Fibonacci Lattice (F({}) = {} points)", size, points.len());
+                (points, title)
+            }
+            _ => (lattice::vogel_disk(size), format!("Sunflower ({} points)", size)),
+        };
+        self.point_set = Some(PointSet { mode, size, points, title });
     }
 
     /// Render the spiral visualization
     pub fn render(
-        &self,
+        &mut self,
         ui: &mut egui::Ui,
         fibonacci_sequence: &[u64],
         current_n: u32,
@@ -101,14 +634,31 @@ impl SpiralVisualization {
         }
 
         ui.group(|ui| {
-            ui.label("Fibonacci Spiral:");
+            ui.horizontal(|ui| {
+                ui.label("Fibonacci Spiral:");
+                ui.add_space(10.0);
+                ui.selectable_value(&mut self.mode, VisualizationMode::Spiral, "Spiral");
+                ui.selectable_value(&mut self.mode, VisualizationMode::Lattice, "Lattice");
+                ui.selectable_value(&mut self.mode, VisualizationMode::Sunflower, "Sunflower");
+            });
             ui.add_space(10.0);
 
             // Create a custom painting area
             let (rect, _response) = ui.allocate_exact_size(Vec2::new(600.0, 400.0), egui::Sense::hover());
 
             if ui.is_rect_visible(rect) {
-                self.drawer.draw_spiral(ui, rect, fibonacci_sequence, current_n);
+                match self.mode {
+                    VisualizationMode::Spiral => {
+                        self.drawer.draw_spiral(ui, rect, fibonacci_sequence, current_n);
+                    }
+                    mode => {
+                        let last = fibonacci_sequence.last().copied().unwrap_or(1);
+                        self.update_point_set(mode, current_n, last);
+                        if let Some(set) = &self.point_set {
+                            self.drawer.draw_point_set(ui, rect, &set.points, &set.title);
+                        }
+                    }
+                }
             }
         });
     }
@@ -119,13 +669,13 @@ pub struct InstructionsPanel;
 
 impl InstructionsPanel {
     /// Render the instructions section
-    pub fn render(ui: &mut egui::Ui) {
+    pub fn render(ui: &mut egui::Ui, max_n: u32) {
         ui.add_space(20.0);
 
         ui.separator();
         ui.small("💡 Tips:");
         ui.small("• Enter a number to see the Fibonacci spiral visualization");
-        ui.small(&format!("• Numbers 0-{} are supported for optimal display", MAX_FIBONACCI_N));
+        ui.small(format!("• Numbers 0-{} are supported; change the limit under ⚙ Settings", max_n));
         ui.small("• Each rectangle's size corresponds to its Fibonacci number");
         ui.small("• The spiral follows the golden ratio pattern");
     }
@@ -143,53 +693,36 @@ impl InstructionsPanel {
                 let second_last_fib = fibonacci_sequence[(current_n - 1) as usize];
                 let golden_ratio = utils::golden_ratio_approximation(last_fib, second_last_fib);
 
-                ui.small(&format!(
+                ui.small(format!(
                     "Golden ratio approximation: F({}) / F({}) ≈ {:.6}",
                     current_n,
                     current_n - 1,
                     golden_ratio
                 ));
 
-                ui.small(&format!(
+                ui.small(format!(
                     "Actual golden ratio (φ): {:.6}",
                     (1.0 + 5.0_f64.sqrt()) / 2.0
                 ));
 
-                // Sum property
-                let sum: u64 = fibonacci_sequence.iter().sum();
-                ui.small(&format!("Sum of all terms: {}", sum));
+                // Sum properties, from closed forms so they never overflow
+                let n = current_n as u64;
+                ui.small(format!(
+                    "Sum of all terms: F({}) - 1 = {}",
+                    current_n + 2,
+                    sums::sum_range_big(0, n)
+                ));
+                ui.small(format!(
+                    "Sum of squares: F({}) × F({}) = {}",
+                    current_n,
+                    current_n + 1,
+                    sums::sum_squares_big(0, n)
+                ));
             });
         }
     }
 }
 
-/// Validation utilities for user input
-pub mod validation {
-    use super::MAX_FIBONACCI_N;
-
-    /// Validate and parse user input
-    pub fn validate_input(input: &str) -> Result<u32, String> {
-        match input.trim().parse::<u32>() {
-            Ok(n) if n <= MAX_FIBONACCI_N => Ok(n),
-            Ok(n) => Err(format!(
-                "Number {} is too large! Please enter 0-{}",
-                n, MAX_FIBONACCI_N
-            )),
-            Err(_) => Err("Please enter a valid number".to_string()),
-        }
-    }
-
-    /// Check if input is valid without parsing
-    pub fn is_valid_input(input: &str) -> bool {
-        validate_input(input).is_ok()
-    }
-
-    /// Get a helpful error message for invalid input
-    pub fn get_error_message(input: &str) -> String {
-        validate_input(input).unwrap_err()
-    }
-}
-
 /// Theme and styling utilities
 pub mod theme {
     use eframe::egui::Color32;
@@ -0,0 +1,212 @@
+//! Input validation shared by every frontend.
+
+use alloc::string::{String, ToString};
+
+use crate::error::InputError;
+
+#[cfg(feature = "bigint")]
+use num_bigint::BigUint;
+
+/// Maximum supported Fibonacci number for optimal display
+pub const MAX_FIBONACCI_N: u32 = 25;
+
+/// Largest n with F(n) representable as a u64
+pub const MAX_U64_N: u32 = 93;
+
+/// Largest n accepted for arbitrary-precision results; F(100000) has 20,899 digits
+pub const MAX_BIG_N: u32 = 100_000;
+
+/// Most digits accepted in an arbitrary-precision value
+pub const MAX_BIG_DIGITS: usize = 1_000;
+
+/// Most terms returned for one index range
+pub const MAX_SEQUENCE_TERMS: u32 = 1_000;
+
+/// Largest modulus accepted for Pisano periods, which walk up to 6m terms
+pub const MAX_PISANO_MODULUS: u64 = 10_000_000;
+
+/// Validate and parse user input
+pub fn validate_input(input: &str) -> Result<u32, InputError> {
+    validate_input_with_max(input, MAX_FIBONACCI_N)
+}
+
+/// Validate and parse user input against a frontend-specific upper limit
+pub fn validate_input_with_max(input: &str, max: u32) -> Result<u32, InputError> {
+    match validate_value(input) {
+        Ok(n) if n <= max as u64 => Ok(n as u32),
+        Ok(_) | Err(InputError::TooLarge { .. }) => Err(too_large(input, max as u64)),
+        Err(error) => Err(error),
+    }
+}
+
+/// Validate and parse a non-negative value such as a candidate Fibonacci number
+///
+/// Only plain digits are accepted, like [`validate_big_value`]; a leading `+`
+/// is not a number here.
+pub fn validate_value(input: &str) -> Result<u64, InputError> {
+    let input = input.trim();
+    if !is_digits(input) {
+        return Err(classify(input));
+    }
+    // All digits but too big for a u64 is still just a large number
+    input.parse::<u64>().map_err(|_| too_large(input, u64::MAX))
+}
+
+/// Validate and parse a non-negative value of any size
+#[cfg(feature = "bigint")]
+pub fn validate_big_value(input: &str) -> Result<BigUint, InputError> {
+    let input = input.trim();
+    if !is_digits(input) {
+        return Err(classify(input));
+    }
+    if input.len() > MAX_BIG_DIGITS {
+        return Err(InputError::TooManyDigits { digits: input.len(), max: MAX_BIG_DIGITS });
+    }
+    input.parse().map_err(|_| InputError::NotANumber)
+}
+
+/// Validate an index range `from..=to` against an index limit and `MAX_SEQUENCE_TERMS`
+pub fn validate_range(from: &str, to: &str, max: u32) -> Result<(u32, u32), InputError> {
+    let (from, to) = (validate_input_with_max(from, max)?, validate_input_with_max(to, max)?);
+    if from > to {
+        return Err(InputError::ReversedRange { from, to });
+    }
+    if to - from >= MAX_SEQUENCE_TERMS {
+        return Err(InputError::RangeTooLong { terms: to - from + 1, max: MAX_SEQUENCE_TERMS });
+    }
+    Ok((from, to))
+}
+
+/// Validate and parse a modulus, which must be at least 1
+pub fn validate_modulus(input: &str) -> Result<u64, InputError> {
+    match validate_value(input)? {
+        0 => Err(InputError::ZeroModulus),
+        m => Ok(m),
+    }
+}
+
+/// Validate a modulus for a Pisano period, between 1 and `MAX_PISANO_MODULUS`
+pub fn validate_pisano_modulus(input: &str) -> Result<u64, InputError> {
+    match validate_modulus(input)? {
+        m if m > MAX_PISANO_MODULUS => Err(InputError::ModulusTooLarge { value: m, max: MAX_PISANO_MODULUS }),
+        m => Ok(m),
+    }
+}
+
+/// Check if input is valid without parsing
+pub fn is_valid_input(input: &str) -> bool {
+    validate_input(input).is_ok()
+}
+
+/// Get a helpful error message for invalid input, or `None` if it is valid
+pub fn get_error_message(input: &str) -> Option<String> {
+    validate_input(input).err().map(|error| error.to_string())
+}
+
+fn is_digits(input: &str) -> bool {
+    !input.is_empty() && input.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn too_large(input: &str, max: u64) -> InputError {
+    InputError::TooLarge { value: input.trim().to_string(), max }
+}
+
+/// Why trimmed input that is not a plain number was rejected
+fn classify(input: &str) -> InputError {
+    match input.strip_prefix('-') {
+        _ if input.is_empty() => InputError::Empty,
+        Some(digits) if is_digits(digits) => InputError::Negative,
+        _ => InputError::NotANumber,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn too_large(value: &str, max: u64) -> Result<u32, InputError> {
+        Err(InputError::TooLarge { value: value.to_string(), max })
+    }
+
+    #[test]
+    fn test_validate_input() {
+        assert_eq!(validate_input(" 10 "), Ok(10));
+        assert_eq!(validate_input("25"), Ok(MAX_FIBONACCI_N));
+        assert_eq!(validate_input("26"), too_large("26", 25));
+        assert_eq!(validate_input("-1"), Err(InputError::Negative));
+        assert_eq!(validate_input("abc"), Err(InputError::NotANumber));
+        assert_eq!(validate_input("-"), Err(InputError::NotANumber));
+        assert_eq!(validate_input(" "), Err(InputError::Empty));
+        assert_eq!(validate_input(" 99999999999999999999999 "), too_large("99999999999999999999999", 25));
+        assert_eq!(
+            validate_input("99999999999").unwrap_err().to_string(),
+            "Number 99999999999 is too large! Please enter 0-25"
+        );
+    }
+
+    #[test]
+    fn test_get_error_message() {
+        assert_eq!(get_error_message("10"), None);
+        assert_eq!(get_error_message("abc"), Some("Please enter a valid number".to_string()));
+    }
+
+    #[test]
+    fn test_validate_input_with_max() {
+        assert_eq!(validate_input_with_max("40", 40), Ok(40));
+        assert_eq!(validate_input_with_max("41", 40), too_large("41", 40));
+    }
+
+    #[test]
+    fn test_validate_value() {
+        assert_eq!(validate_value("18446744073709551615"), Ok(u64::MAX));
+        assert!(matches!(validate_value("18446744073709551616"), Err(InputError::TooLarge { max: u64::MAX, .. })));
+        assert_eq!(validate_value("-5"), Err(InputError::Negative));
+        assert_eq!(validate_modulus("0"), Err(InputError::ZeroModulus));
+        assert_eq!(validate_modulus("7"), Ok(7));
+        assert_eq!(validate_pisano_modulus("10000000"), Ok(MAX_PISANO_MODULUS));
+        assert_eq!(
+            validate_pisano_modulus("10000001"),
+            Err(InputError::ModulusTooLarge { value: 10_000_001, max: MAX_PISANO_MODULUS })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn test_validate_big_value() {
+        let value = validate_big_value(" 18446744073709551616 ").unwrap();
+        assert_eq!(value, BigUint::from(u64::MAX) + 1u32);
+        assert_eq!(validate_big_value("+5"), Err(InputError::NotANumber));
+        assert_eq!(validate_big_value(""), Err(InputError::Empty));
+        assert!(validate_big_value(&"9".repeat(MAX_BIG_DIGITS)).is_ok());
+        assert_eq!(
+            validate_big_value(&"9".repeat(MAX_BIG_DIGITS + 1)),
+            Err(InputError::TooManyDigits { digits: MAX_BIG_DIGITS + 1, max: MAX_BIG_DIGITS })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn test_value_parsers_agree() {
+        for input in ["0", " 42 ", "18446744073709551615", "+5", "-5", "5 5", "0x10", "", "٣"] {
+            assert_eq!(
+                validate_value(input).map(BigUint::from),
+                validate_big_value(input),
+                "{:?}",
+                input
+            );
+        }
+        assert_eq!(validate_input("+5"), Err(InputError::NotANumber));
+    }
+
+    #[test]
+    fn test_validate_range() {
+        assert_eq!(validate_range("5", "10", MAX_BIG_N), Ok((5, 10)));
+        assert_eq!(validate_range("3", "2", MAX_BIG_N), Err(InputError::ReversedRange { from: 3, to: 2 }));
+        assert!(validate_range("0", "94", MAX_U64_N).is_err());
+        assert!(validate_range("0", "999", MAX_BIG_N).is_ok());
+        assert_eq!(
+            validate_range("0", "1000", MAX_BIG_N),
+            Err(InputError::RangeTooLong { terms: 1001, max: MAX_SEQUENCE_TERMS })
+        );
+    }
+}
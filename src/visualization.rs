@@ -1,16 +1,20 @@
-use eframe::egui::{self, Color32, Pos2, Rect, Stroke, Vec2};
-
-/// Colors for the Fibonacci spiral rectangles (golden/yellow theme)
-pub const FIBONACCI_COLORS: [Color32; 8] = [
-    Color32::from_rgb(255, 255, 200), // Light yellow
-    Color32::from_rgb(255, 245, 180), // Cream
-    Color32::from_rgb(255, 235, 160), // Light gold
-    Color32::from_rgb(255, 225, 140), // Gold
-    Color32::from_rgb(255, 215, 120), // Darker gold
-    Color32::from_rgb(255, 205, 100), // Orange-gold
-    Color32::from_rgb(255, 195, 80),  // Deep gold
-    Color32::from_rgb(255, 185, 60),  // Amber
-];
+#[cfg(feature = "egui-app")]
+use eframe::egui::{self, Color32, Stroke};
+use emath::{Pos2, Rect, Vec2};
+
+/// Colors for the Fibonacci spiral rectangles (golden/yellow theme), built
+/// from the settings file's default `display.colors`
+#[cfg(feature = "egui-app")]
+pub const FIBONACCI_COLORS: [Color32; 8] = {
+    let mut colors = [Color32::BLACK; 8];
+    let mut i = 0;
+    while i < colors.len() {
+        let [r, g, b] = crate::settings::DEFAULT_COLORS[i].0;
+        colors[i] = Color32::from_rgb(r, g, b);
+        i += 1;
+    }
+    colors
+};
 
 /// Represents a rectangle in the Fibonacci spiral
 #[derive(Debug, Clone)]
@@ -20,27 +24,106 @@ pub struct FibonacciRectangle {
     pub index: usize,
 }
 
+/// Largest on-screen side of a unit square, so short sequences stay small
+const MAX_UNIT_SIZE: f32 = 42.0;
+
+/// Calculate the positions and sizes of all rectangles in the spiral
+///
+/// Rectangles are laid out around the first two unit squares, turning
+/// down, left, up and right in turn. Each new rectangle borders the spiral
+/// built so far, so none overlap, and the whole spiral is scaled to fit and
+/// centered in `rect`.
+pub fn spiral_rectangles(rect: Rect, fibonacci_sequence: &[u64]) -> Vec<FibonacciRectangle> {
+    if fibonacci_sequence.len() < 3 {
+        return Vec::new();
+    }
+
+    // Lay the spiral out with sides of sqrt(F(i)) first, which keeps small
+    // terms visible next to large ones, and scale it to the target afterwards
+    let mut temp_rectangles = Vec::new();
+
+    // Start with the first two 1x1 squares at origin (0,0)
+    let rect1 = Rect::from_min_size(Pos2::new(0.0, 0.0), Vec2::splat(1.0));
+    temp_rectangles.push((rect1, 1, 0));
+
+    let rect2 = Rect::from_min_size(Pos2::new(1.0, 0.0), Vec2::splat(1.0));
+    temp_rectangles.push((rect2, 1, 1));
+
+    // The bounding box of the spiral so far
+    let mut bounds = rect1.union(rect2);
+
+    for (i, &fib_val) in fibonacci_sequence.iter().enumerate().take(12).skip(2) {
+        let size = (fib_val as f32).sqrt();
+
+        let new_rect = match (i - 2) % 4 {
+            // down
+            0 => Rect::from_min_size(bounds.left_bottom(), Vec2::new(bounds.width(), size)),
+            // left
+            1 => Rect::from_min_size(bounds.min - Vec2::new(size, 0.0), Vec2::new(size, bounds.height())),
+            // up
+            2 => Rect::from_min_size(bounds.min - Vec2::new(0.0, size), Vec2::new(bounds.width(), size)),
+            // right
+            _ => Rect::from_min_size(bounds.right_top(), Vec2::new(size, bounds.height())),
+        };
+
+        temp_rectangles.push((new_rect, fib_val, i));
+        bounds = bounds.union(new_rect);
+    }
+
+    // Fit the spiral into the available area and center it there
+    let scale = (rect.width() / bounds.width())
+        .min(rect.height() / bounds.height())
+        .clamp(0.0, MAX_UNIT_SIZE);
+
+    temp_rectangles
+        .into_iter()
+        .map(|(temp_rect, fib_val, index)| FibonacciRectangle {
+            rect: Rect::from_min_size(
+                rect.center() + (temp_rect.min - bounds.center()) * scale,
+                temp_rect.size() * scale,
+            ),
+            value: fib_val,
+            index,
+        })
+        .collect()
+}
+
 /// Spiral drawer for Fibonacci visualization
+#[cfg(feature = "egui-app")]
 pub struct SpiralDrawer {
     pub grid_size: f32,
     pub grid_color: Color32,
+    /// Fill colors of the squares, repeated as needed
+    pub colors: Vec<Color32>,
 }
 
+#[cfg(feature = "egui-app")]
 impl Default for SpiralDrawer {
     fn default() -> Self {
         Self {
             grid_size: 10.0,
             grid_color: Color32::from_rgba_unmultiplied(200, 200, 200, 100),
+            colors: FIBONACCI_COLORS.to_vec(),
         }
     }
 }
 
+#[cfg(feature = "egui-app")]
 impl SpiralDrawer {
     /// Create a new spiral drawer with custom grid settings
     pub fn new(grid_size: f32, grid_color: Color32) -> Self {
         Self {
             grid_size,
             grid_color,
+            colors: FIBONACCI_COLORS.to_vec(),
+        }
+    }
+
+    /// Fill color of the `i`th square
+    fn color(&self, i: usize) -> Color32 {
+        match self.colors.len() {
+            0 => FIBONACCI_COLORS[i % FIBONACCI_COLORS.len()],
+            len => self.colors[i % len],
         }
     }
 
@@ -59,145 +142,61 @@ impl SpiralDrawer {
         }
 
         // Draw grid background
-        self.draw_grid(rect, &painter);
+        self.draw_grid(rect, painter);
 
         // Calculate and draw rectangles
-        let rectangles = self.calculate_spiral_rectangles(rect, fibonacci_sequence);
-        self.draw_rectangles(&painter, &rectangles);
+        let rectangles = spiral_rectangles(rect, fibonacci_sequence);
+        self.draw_rectangles(painter, &rectangles);
 
         // Draw title
-        self.draw_title(&painter, rect, current_n);
+        self.draw_title(painter, rect, &format!("Fibonacci Spiral (n = {})", current_n));
     }
 
-    /// Calculate the positions and sizes of all rectangles in the spiral
-    fn calculate_spiral_rectangles(&self, rect: Rect, fibonacci_sequence: &[u64]) -> Vec<FibonacciRectangle> {
-        let mut rectangles = Vec::new();
-
-        if fibonacci_sequence.len() < 3 {
-            return rectangles;
-        }
-
-        // Calculate scale factor to ensure smaller numbers are visible
-        // Use a more conservative scaling to prevent overlapping
-        let max_fib = fibonacci_sequence.iter().max().unwrap_or(&1);
-        let available_size = rect.width().min(rect.height()) * 0.5;
-
-        // Base unit size - ensure even F(1)=1 is readable but not too large
-        let min_unit_size: f32 = 20.0;
-        let max_unit_size = available_size / (*max_fib as f32).sqrt();
-        let unit = min_unit_size.max(max_unit_size.min(35.0));
-
-        // Build all rectangles first to calculate total bounds
-        let mut temp_rectangles = Vec::new();
-
-        // Start with the first two 1x1 squares at origin (0,0)
-        // Use the same square root scaling for consistency but more conservative
-        let first_size = (1.0_f32).sqrt() * unit * 1.2;
-        let rect1 = Rect::from_min_size(Pos2::new(0.0, 0.0), Vec2::splat(first_size));
-        temp_rectangles.push((rect1, 1, 0));
-
-        let rect2 = Rect::from_min_size(Pos2::new(first_size, 0.0), Vec2::splat(first_size));
-        temp_rectangles.push((rect2, 1, 1));
-
-        // Build the spiral outward from origin
-        let mut current_width = first_size * 2.0;
-        let mut current_height = first_size;
-        let mut base_x = 0.0;
-        let mut base_y = 0.0;
-
-        for i in 2..fibonacci_sequence.len().min(12) {
-            let fib_val = fibonacci_sequence[i];
-            // Use square root scaling to make smaller numbers more visible
-            // while still maintaining proportional relationships
-            let size = (fib_val as f32).sqrt() * unit * 1.2;
-            let direction_idx = (i - 2) % 4;
-
-            let (new_rect, new_base_x, new_base_y, new_width, new_height) = match direction_idx {
-                0 => {
-                    // down
-                    let r = Rect::from_min_size(
-                        Pos2::new(base_x, base_y + current_height),
-                        Vec2::new(current_width, size),
-                    );
-                    (r, base_x, base_y, current_width, current_height + size)
-                }
-                1 => {
-                    // left
-                    let r = Rect::from_min_size(
-                        Pos2::new(base_x - size, base_y),
-                        Vec2::new(size, current_height),
-                    );
-                    (r, base_x - size, base_y, current_width + size, current_height)
-                }
-                2 => {
-                    // up
-                    let r = Rect::from_min_size(
-                        Pos2::new(base_x, base_y - size),
-                        Vec2::new(current_width, size),
-                    );
-                    (r, base_x, base_y - size, current_width, current_height + size)
-                }
-                3 => {
-                    // right
-                    let r = Rect::from_min_size(
-                        Pos2::new(base_x + current_width, base_y),
-                        Vec2::new(size, current_height),
-                    );
-                    (r, base_x, base_y, current_width + size, current_height)
-                }
-                _ => continue,
-            };
-
-            temp_rectangles.push((new_rect, fib_val, i));
-            base_x = new_base_x;
-            base_y = new_base_y;
-            current_width = new_width;
-            current_height = new_height;
-        }
-
-        // Calculate the bounding box of all rectangles
-        let mut min_x = f32::INFINITY;
-        let mut min_y = f32::INFINITY;
-        let mut max_x = f32::NEG_INFINITY;
-        let mut max_y = f32::NEG_INFINITY;
-
-        for (temp_rect, _, _) in &temp_rectangles {
-            min_x = min_x.min(temp_rect.min.x);
-            min_y = min_y.min(temp_rect.min.y);
-            max_x = max_x.max(temp_rect.max.x);
-            max_y = max_y.max(temp_rect.max.y);
-        }
-
-        let spiral_width = max_x - min_x;
-        let spiral_height = max_y - min_y;
-
-        // Calculate offset to center the spiral in the available area
-        let center_x = rect.center().x;
-        let center_y = rect.center().y;
-        let offset_x = center_x - (min_x + spiral_width / 2.0);
-        let offset_y = center_y - (min_y + spiral_height / 2.0);
+    /// Draw a 2D point set such as a Fibonacci lattice or sunflower, scaled to fit the rect
+    pub fn draw_point_set(&self, ui: &mut egui::Ui, rect: Rect, points: &[[f64; 2]], title: &str) {
+        let painter = ui.painter();
 
-        // Apply the centering offset to all rectangles
-        for (temp_rect, fib_val, index) in temp_rectangles {
-            let centered_rect = Rect::from_min_size(
-                Pos2::new(temp_rect.min.x + offset_x, temp_rect.min.y + offset_y),
-                temp_rect.size(),
-            );
+        self.draw_grid(rect, painter);
+
+        if !points.is_empty() {
+            // Fit the bounding box of the points into a centered square
+            let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+            let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+            for p in points {
+                min_x = min_x.min(p[0]);
+                min_y = min_y.min(p[1]);
+                max_x = max_x.max(p[0]);
+                max_y = max_y.max(p[1]);
+            }
 
-            rectangles.push(FibonacciRectangle {
-                rect: centered_rect,
-                value: fib_val,
-                index,
-            });
+            let extent = (max_x - min_x).max(max_y - min_y);
+            let side = rect.width().min(rect.height()) * 0.85;
+            let scale = if extent > 0.0 { side / extent as f32 } else { 0.0 };
+            let center = rect.center();
+            let mid_x = (min_x + max_x) / 2.0;
+            let mid_y = (min_y + max_y) / 2.0;
+
+            // Shrink dots as the set gets denser
+            let radius = (side / (points.len() as f32).sqrt() * 0.3).clamp(1.5, 6.0);
+            let color = self.color(self.colors.len().saturating_sub(1));
+
+            for p in points {
+                let pos = Pos2::new(
+                    center.x + (p[0] - mid_x) as f32 * scale,
+                    // Screen y grows downwards
+                    center.y - (p[1] - mid_y) as f32 * scale,
+                );
+                painter.circle(pos, radius, color, Stroke::new(0.5, Color32::BLACK));
+            }
         }
 
-        rectangles
+        self.draw_title(painter, rect, title);
     }
 
     /// Draw all rectangles with their numbers
     fn draw_rectangles(&self, painter: &egui::Painter, rectangles: &[FibonacciRectangle]) {
         for (i, fib_rect) in rectangles.iter().enumerate() {
-            let color = FIBONACCI_COLORS[i % FIBONACCI_COLORS.len()];
+            let color = self.color(i);
 
             // Draw rectangle
             painter.rect_filled(fib_rect.rect, 2.0, color);
@@ -285,8 +284,7 @@ impl SpiralDrawer {
     }
 
     /// Draw the title
-    fn draw_title(&self, painter: &egui::Painter, rect: Rect, current_n: u32) {
-        let title_text = format!("Fibonacci Spiral (n = {})", current_n);
+    fn draw_title(&self, painter: &egui::Painter, rect: Rect, title_text: &str) {
         let title_pos = Pos2::new(rect.min.x + 15.0, rect.min.y + 15.0);
 
         // Draw title background for better readability
@@ -295,7 +293,7 @@ impl SpiralDrawer {
 
         // Estimate text bounds for background
         let text_galley = painter.layout_no_wrap(
-            title_text.clone(),
+            title_text.to_string(),
             font_id.clone(),
             Color32::BLACK,
         );
@@ -330,6 +328,48 @@ impl SpiralDrawer {
 
 /// Utility functions for visualization
 pub mod utils {
+    /// Text layouts for showing a sequence
+    ///
+    /// File formats with analytics columns live in `crate::export`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    #[cfg_attr(feature = "egui-app", derive(serde::Serialize, serde::Deserialize))]
+    pub enum SequenceFormat {
+        /// `F(0) = 0, F(1) = 1, ...`
+        #[default]
+        Labeled,
+        /// `0, 1, 1, 2, ...`
+        Values,
+        /// One aligned `n  F(n)  ratio` line per term
+        Table,
+    }
+
+    impl SequenceFormat {
+        /// All formats, in display order
+        pub const ALL: [SequenceFormat; 3] = [
+            SequenceFormat::Labeled,
+            SequenceFormat::Values,
+            SequenceFormat::Table,
+        ];
+
+        /// Short label for selector buttons
+        pub fn label(&self) -> &'static str {
+            match self {
+                SequenceFormat::Labeled => "F(n) = …",
+                SequenceFormat::Values => "Values",
+                SequenceFormat::Table => "Table",
+            }
+        }
+
+        /// Format a sequence starting at F(0)
+        pub fn format(&self, sequence: &[u64]) -> String {
+            match self {
+                SequenceFormat::Labeled => format_sequence(sequence),
+                SequenceFormat::Values => format_values(sequence),
+                SequenceFormat::Table => format_table(sequence),
+            }
+        }
+    }
+
     /// Format a Fibonacci sequence for display
     pub fn format_sequence(sequence: &[u64]) -> String {
         sequence
@@ -340,6 +380,31 @@ pub mod utils {
             .join(", ")
     }
 
+    /// Format just the values, comma separated
+    pub fn format_values(sequence: &[u64]) -> String {
+        sequence
+            .iter()
+            .map(u64::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Format one line per term with the ratio to the previous term
+    pub fn format_table(sequence: &[u64]) -> String {
+        let index_width = sequence.len().saturating_sub(1).to_string().len().max(1);
+        let value_width = sequence.iter().max().map_or(1, |v| v.to_string().len()).max(4);
+
+        let mut lines = vec![format!("{:>index_width$}  {:>value_width$}  ratio", "n", "F(n)")];
+        for (i, &value) in sequence.iter().enumerate() {
+            let ratio = match i.checked_sub(1).map(|prev| sequence[prev]) {
+                Some(prev) if prev != 0 => format!("{:.6}", golden_ratio_approximation(value, prev)),
+                _ => "-".to_string(),
+            };
+            lines.push(format!("{:>index_width$}  {:>value_width$}  {}", i, value, ratio));
+        }
+        lines.join("\n")
+    }
+
     /// Calculate the golden ratio approximation from two consecutive Fibonacci numbers
     pub fn golden_ratio_approximation(fib_n: u64, fib_n_minus_1: u64) -> f64 {
         if fib_n_minus_1 == 0 {
@@ -362,3 +427,19 @@ pub mod utils {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::utils::*;
+
+    #[test]
+    fn test_sequence_formats() {
+        let sequence = [0, 1, 1, 2, 3];
+        assert_eq!(SequenceFormat::Labeled.format(&sequence), "F(0) = 0, F(1) = 1, F(2) = 1, F(3) = 2, F(4) = 3");
+        assert_eq!(SequenceFormat::Values.format(&sequence), "0, 1, 1, 2, 3");
+        assert_eq!(
+            SequenceFormat::Table.format(&sequence),
+            "n  F(n)  ratio\n0     0  -\n1     1  -\n2     1  1.000000\n3     2  2.000000\n4     3  1.500000"
+        );
+    }
+}
@@ -0,0 +1,206 @@
+//! Run one computation at a time off the UI thread.
+//!
+//! [`Worker::start`] moves a job onto a background thread and hands the
+//! result back over a channel; the UI calls [`Worker::poll`] once per frame.
+//! Jobs report progress and check for cancellation through [`Progress`].
+//! Starting a job cancels the one in flight, so only the latest request ever
+//! delivers a result.
+//!
+//! Browsers have no threads, so on wasm32 jobs run to completion inside
+//! `start` and the result is ready on the next `poll`.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+/// A running job's view of its progress and cancellation
+#[derive(Debug, Clone, Default)]
+pub struct Progress {
+    cancelled: Arc<AtomicBool>,
+    /// Fraction done as `f32` bits
+    fraction: Arc<AtomicU32>,
+}
+
+impl Progress {
+    /// Record how much of the job is done, from 0.0 to 1.0
+    pub fn set(&self, fraction: f32) {
+        self.fraction.store(fraction.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Fraction done, from 0.0 to 1.0
+    pub fn get(&self) -> f32 {
+        f32::from_bits(self.fraction.load(Ordering::Relaxed))
+    }
+
+    /// Check if the job should stop; a job that sees this returns `None`
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+struct Job<T> {
+    receiver: Receiver<Option<T>>,
+    progress: Progress,
+}
+
+/// Runs jobs returning `T` one at a time in the background
+pub struct Worker<T> {
+    current: Option<Job<T>>,
+}
+
+impl<T> Default for Worker<T> {
+    fn default() -> Self {
+        Self { current: None }
+    }
+}
+
+impl<T: Send + 'static> Worker<T> {
+    /// Start a job, cancelling the one in flight
+    ///
+    /// The job should check [`Progress::is_cancelled`] regularly and return
+    /// `None` once it is set.
+    pub fn start(&mut self, job: impl FnOnce(&Progress) -> Option<T> + Send + 'static) {
+        self.cancel();
+        let (sender, receiver) = mpsc::channel();
+        let progress = Progress::default();
+        let handle = progress.clone();
+        let run = move || {
+            let result = job(&handle);
+            // The receiver is gone if the job was superseded, which is fine
+            let _ = sender.send(result.filter(|_| !handle.is_cancelled()));
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        std::thread::spawn(run);
+        #[cfg(target_arch = "wasm32")]
+        run();
+
+        self.current = Some(Job { receiver, progress });
+    }
+
+    /// Take the result of a finished job, if there is one
+    pub fn poll(&mut self) -> Option<T> {
+        let job = self.current.as_ref()?;
+        match job.receiver.try_recv() {
+            Err(TryRecvError::Empty) => None,
+            // A cancelled job sends `None`; a job that panicked sends nothing
+            Ok(result) => {
+                self.current = None;
+                result
+            }
+            Err(TryRecvError::Disconnected) => {
+                self.current = None;
+                None
+            }
+        }
+    }
+
+    /// Block until the running job finishes and take its result
+    pub fn wait(&mut self) -> Option<T> {
+        let job = self.current.take()?;
+        job.receiver.recv().ok().flatten()
+    }
+}
+
+impl<T> Worker<T> {
+    /// Ask the running job to stop and drop its result
+    pub fn cancel(&mut self) {
+        if let Some(job) = self.current.take() {
+            job.progress.cancel();
+        }
+    }
+
+    /// Check if a job is in flight
+    pub fn is_running(&self) -> bool {
+        self.current.is_some()
+    }
+
+    /// Fraction of the running job that is done, or `None` if idle
+    pub fn progress(&self) -> Option<f32> {
+        self.current.as_ref().map(|job| job.progress.get())
+    }
+}
+
+impl<T> Drop for Worker<T> {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::Sender;
+    use std::time::Duration;
+
+    /// A job that counts to `n`, pausing on `gate` before each step
+    fn counting(n: u32, gate: Receiver<()>) -> impl FnOnce(&Progress) -> Option<u32> + Send + 'static {
+        move |progress| {
+            for i in 0..n {
+                if gate.recv().is_err() || progress.is_cancelled() {
+                    return None;
+                }
+                progress.set((i + 1) as f32 / n as f32);
+            }
+            Some(n)
+        }
+    }
+
+    fn open(gate: &Sender<()>, steps: usize) {
+        for _ in 0..steps {
+            let _ = gate.send(());
+        }
+    }
+
+    #[test]
+    fn test_result_and_progress() {
+        let mut worker = Worker::default();
+        assert!(!worker.is_running());
+        assert_eq!(worker.poll(), None);
+
+        let (gate, steps) = mpsc::channel();
+        worker.start(counting(4, steps));
+        assert!(worker.is_running());
+        assert_eq!(worker.poll(), None);
+        open(&gate, 2);
+        while worker.progress() != Some(0.5) {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        open(&gate, 2);
+        assert_eq!(worker.wait(), Some(4));
+        assert!(!worker.is_running());
+        assert_eq!(worker.progress(), None);
+    }
+
+    #[test]
+    fn test_new_job_supersedes() {
+        let mut worker = Worker::default();
+        let (first_gate, steps) = mpsc::channel();
+        worker.start(counting(3, steps));
+        let (second_gate, steps) = mpsc::channel();
+        worker.start(counting(2, steps));
+
+        // Finishing the first job no longer delivers anything
+        open(&first_gate, 3);
+        open(&second_gate, 2);
+        assert_eq!(worker.wait(), Some(2));
+        assert_eq!(worker.poll(), None);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut worker = Worker::default();
+        let (gate, steps) = mpsc::channel();
+        worker.start(counting(3, steps));
+        worker.cancel();
+        assert!(!worker.is_running());
+        open(&gate, 3);
+        assert_eq!(worker.wait(), None);
+        assert_eq!(worker.poll(), None);
+    }
+}
@@ -0,0 +1,299 @@
+#[cfg(feature = "alloc")]
+use alloc::{format, string::{String, ToString}, vec, vec::Vec};
+use core::iter::FusedIterator;
+
+#[cfg(feature = "bigint")]
+use num_bigint::BigUint;
+#[cfg(feature = "bigint")]
+use num_traits::One;
+
+/// Iterator over the Zeckendorf indices of a number, largest first
+///
+/// Created by [`zeckendorf_terms`]; works without an allocator.
+#[derive(Debug, Clone)]
+pub struct ZeckendorfTerms {
+    remaining: u64,
+    /// F(index - 1) and F(index)
+    previous: u64,
+    current: u64,
+    index: u32,
+}
+
+impl Iterator for ZeckendorfTerms {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        while self.remaining > 0 {
+            let (index, current) = (self.index, self.current);
+            (self.previous, self.current) = (self.current - self.previous, self.previous);
+            self.index -= 1;
+
+            if current <= self.remaining {
+                self.remaining -= current;
+                return Some(index);
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for ZeckendorfTerms {}
+
+/// Decompose n into non-consecutive Fibonacci numbers without allocating
+///
+/// Yields the same indices as [`zeckendorf`], largest first.
+///
+/// # Examples
+/// ```
+/// use fibonacci_sequence::zeckendorf::zeckendorf_terms;
+///
+/// let mut terms = zeckendorf_terms(100);
+/// assert_eq!(terms.next(), Some(11));
+/// assert_eq!(terms.next(), Some(6));
+/// assert_eq!(terms.next(), Some(4));
+/// assert_eq!(terms.next(), None);
+/// ```
+pub fn zeckendorf_terms(n: u64) -> ZeckendorfTerms {
+    // Climb to the largest F(index) <= n, starting from F(2) = 1
+    let (mut previous, mut current, mut index) = (1u64, 1u64, 2u32);
+    while let Some(next) = previous.checked_add(current).filter(|&next| next <= n) {
+        (previous, current) = (current, next);
+        index += 1;
+    }
+    ZeckendorfTerms { remaining: n, previous, current, index }
+}
+
+/// Decompose n into a sum of non-consecutive Fibonacci numbers
+///
+/// # Returns
+/// The Fibonacci indices (all >= 2) of the terms, largest first.
+/// Zero has an empty decomposition.
+///
+/// # Examples
+/// ```
+/// use fibonacci_sequence::zeckendorf::zeckendorf;
+///
+/// // 100 = 89 + 8 + 3 = F(11) + F(6) + F(4)
+/// assert_eq!(zeckendorf(100), vec![11, 6, 4]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn zeckendorf(n: u64) -> Vec<u32> {
+    zeckendorf_terms(n).collect()
+}
+
+/// Format n in the Fibonacci base (Zeckendorf digits, least significant digit is F(2))
+///
+/// The result never contains two adjacent `1`s.
+///
+/// # Examples
+/// ```
+/// use fibonacci_sequence::zeckendorf::to_fibonacci_base;
+///
+/// assert_eq!(to_fibonacci_base(0), "0");
+/// assert_eq!(to_fibonacci_base(4), "101");
+/// assert_eq!(to_fibonacci_base(100), "1000010100");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn to_fibonacci_base(n: u64) -> String {
+    digits_to_string(&zeckendorf(n))
+}
+
+/// Decompose an arbitrary-precision integer into non-consecutive Fibonacci numbers
+///
+/// Returns the Fibonacci indices of the terms, largest first, like [`zeckendorf`].
+#[cfg(feature = "bigint")]
+pub fn zeckendorf_big(n: &BigUint) -> Vec<u32> {
+    let mut fibs = vec![BigUint::one()];
+    let mut next = BigUint::from(2u32);
+    while &next <= n {
+        let following = &next + &fibs[fibs.len() - 1];
+        fibs.push(next);
+        next = following;
+    }
+
+    let mut remaining = n.clone();
+    let mut indices = Vec::new();
+    for (i, f) in fibs.iter().enumerate().rev() {
+        if f <= &remaining {
+            remaining -= f;
+            indices.push(i as u32 + 2);
+        }
+    }
+    indices
+}
+
+/// Format an arbitrary-precision integer in the Fibonacci base
+#[cfg(feature = "bigint")]
+pub fn to_fibonacci_base_big(n: &BigUint) -> String {
+    digits_to_string(&zeckendorf_big(n))
+}
+
+/// Turn descending Fibonacci indices (>= 2) into a digit string
+#[cfg(feature = "alloc")]
+fn digits_to_string(indices: &[u32]) -> String {
+    let Some(&top) = indices.first() else {
+        return "0".to_string();
+    };
+    let mut digits = vec!['0'; (top - 1) as usize];
+    for &i in indices {
+        digits[(top - i) as usize] = '1';
+    }
+    digits.into_iter().collect()
+}
+
+/// Parse a Fibonacci-base digit string back into an integer
+///
+/// Non-canonical strings (with adjacent `1`s) are accepted and summed as written.
+#[cfg(feature = "alloc")]
+pub fn from_fibonacci_base(digits: &str) -> Result<u64, String> {
+    let digits = digits.trim();
+    if digits.is_empty() {
+        return Err("Fibonacci-base number is empty".to_string());
+    }
+
+    // F(p + 2) and F(p + 3) for the current position p, None once they overflow
+    let (mut weight, mut next) = (Some(1u64), Some(2u64));
+    let mut total = 0u64;
+    for c in digits.chars().rev() {
+        match c {
+            '0' => {}
+            '1' => {
+                total = weight
+                    .and_then(|w| total.checked_add(w))
+                    .ok_or_else(|| "Fibonacci-base number is too large".to_string())?;
+            }
+            _ => return Err(format!("Invalid Fibonacci-base digit '{}'", c)),
+        }
+        let following = weight.zip(next).and_then(|(a, b)| a.checked_add(b));
+        (weight, next) = (next, following);
+    }
+    Ok(total)
+}
+
+/// Encode n >= 1 with the Fibonacci universal code
+///
+/// The Zeckendorf digits are written least significant first and terminated
+/// by an extra `1`, so every codeword ends in `11` and no other `11` appears.
+///
+/// # Examples
+/// ```
+/// use fibonacci_sequence::zeckendorf::fibonacci_encode;
+///
+/// assert_eq!(fibonacci_encode(1), Some("11".to_string()));
+/// assert_eq!(fibonacci_encode(4), Some("1011".to_string()));
+/// assert_eq!(fibonacci_encode(0), None);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn fibonacci_encode(n: u64) -> Option<String> {
+    if n == 0 {
+        return None;
+    }
+    let mut code: String = to_fibonacci_base(n).chars().rev().collect();
+    code.push('1');
+    Some(code)
+}
+
+/// Decode a stream of concatenated Fibonacci codewords
+///
+/// # Returns
+/// The decoded values, or an error if the stream contains invalid characters,
+/// an incomplete final codeword, or a value that does not fit in a u64.
+#[cfg(feature = "alloc")]
+pub fn fibonacci_decode(bits: &str) -> Result<Vec<u64>, String> {
+    let mut values = Vec::new();
+    let mut current = String::new();
+    let mut previous_one = false;
+
+    for c in bits.chars() {
+        match c {
+            '1' if previous_one => {
+                let digits: String = current.chars().rev().collect();
+                values.push(from_fibonacci_base(&digits)?);
+                current.clear();
+                previous_one = false;
+            }
+            '0' | '1' => {
+                current.push(c);
+                previous_one = c == '1';
+            }
+            _ => return Err(format!("Invalid Fibonacci code bit '{}'", c)),
+        }
+    }
+
+    if !current.is_empty() {
+        return Err("Fibonacci code stream ends in the middle of a codeword".to_string());
+    }
+    Ok(values)
+}
+
+/// Check that a decomposition is a valid Zeckendorf representation
+pub fn is_zeckendorf(indices: &[u32]) -> bool {
+    indices.iter().all(|&i| i >= 2) && indices.windows(2).all(|w| w[0] >= w[1] + 2)
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::fibonacci;
+
+    #[test]
+    fn test_zeckendorf_sums_back() {
+        for n in 0..2000u64 {
+            let indices = zeckendorf(n);
+            assert!(is_zeckendorf(&indices), "{} -> {:?}", n, indices);
+            let total: u64 = indices.iter().map(|&i| fibonacci::fib_checked(i).unwrap()).sum();
+            assert_eq!(total, n);
+        }
+    }
+
+    #[test]
+    fn test_zeckendorf_of_fibonacci_numbers() {
+        for i in 2..=93 {
+            let f = fibonacci::fib_checked(i).unwrap();
+            // F(2) = F(1) = 1 is represented by index 2
+            assert_eq!(zeckendorf(f), vec![i.max(2)]);
+        }
+        assert_eq!(zeckendorf(u64::MAX)[0], 93);
+    }
+
+    #[test]
+    fn test_fibonacci_base_round_trip() {
+        for n in (0..5000u64).chain([u64::MAX, u64::MAX - 1, 12200160415121876738]) {
+            let digits = to_fibonacci_base(n);
+            assert!(!digits.contains("11"));
+            assert_eq!(from_fibonacci_base(&digits), Ok(n));
+            #[cfg(feature = "bigint")]
+            assert_eq!(to_fibonacci_base_big(&BigUint::from(n)), digits);
+            #[cfg(feature = "bigint")]
+            assert_eq!(zeckendorf_big(&BigUint::from(n)), zeckendorf(n));
+        }
+    }
+
+    #[test]
+    fn test_from_fibonacci_base_errors() {
+        assert!(from_fibonacci_base("").is_err());
+        assert!(from_fibonacci_base("102").is_err());
+        assert!(from_fibonacci_base(&"1".repeat(200)).is_err());
+        assert_eq!(from_fibonacci_base("11"), Ok(3));
+    }
+
+    #[test]
+    fn test_fibonacci_code() {
+        assert_eq!(fibonacci_encode(1).unwrap(), "11");
+        assert_eq!(fibonacci_encode(2).unwrap(), "011");
+        assert_eq!(fibonacci_encode(3).unwrap(), "0011");
+        assert_eq!(fibonacci_encode(11).unwrap(), "001011");
+
+        let values: Vec<u64> = (1..300).chain([u64::MAX]).collect();
+        let stream: String = values.iter().map(|&v| fibonacci_encode(v).unwrap()).collect();
+        assert_eq!(fibonacci_decode(&stream), Ok(values));
+    }
+
+    #[test]
+    fn test_fibonacci_decode_errors() {
+        assert_eq!(fibonacci_decode(""), Ok(vec![]));
+        assert!(fibonacci_decode("0101").is_err());
+        assert!(fibonacci_decode("11x").is_err());
+        assert!(fibonacci_decode(&format!("{}11", "01".repeat(100))).is_err());
+    }
+}
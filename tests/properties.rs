@@ -0,0 +1,161 @@
+//! Generated-input checks that the algorithms agree with each other and with
+//! the classical identities, and that validation and layout hold for any input.
+#![cfg(all(feature = "std", feature = "bigint"))]
+
+use emath::{Pos2, Rect, Vec2};
+use num_bigint::{BigInt, BigUint};
+use proptest::prelude::*;
+
+use fibonacci_sequence::error::InputError;
+use fibonacci_sequence::fibonacci;
+use fibonacci_sequence::validation::{self, MAX_FIBONACCI_N, MAX_U64_N};
+use fibonacci_sequence::visualization::spiral_rectangles;
+
+/// Largest n the exponential algorithms finish quickly for
+const RECURSIVE_MAX_N: u32 = 25;
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+proptest! {
+    #[test]
+    fn recursive_algorithms_agree(n in 0..=RECURSIVE_MAX_N) {
+        let expected = fibonacci::fib(n);
+        prop_assert_eq!(fibonacci::fib_memoized(n), expected);
+        prop_assert_eq!(fibonacci::fib_checked(n), Some(expected));
+        prop_assert_eq!(fibonacci::generate_sequence(n), fibonacci::generate_sequence_iterative(n));
+    }
+
+    #[test]
+    fn fast_algorithms_agree(n in 0..=MAX_U64_N) {
+        let sequence = fibonacci::generate_sequence_iterative(n);
+        let expected = sequence[n as usize];
+        prop_assert_eq!(sequence.len(), n as usize + 1);
+        prop_assert_eq!(fibonacci::fib_memoized(n), expected);
+        prop_assert_eq!(fibonacci::fib_checked(n), Some(expected));
+        prop_assert_eq!(fibonacci::fib_u128(n), Some(expected as u128));
+        prop_assert_eq!(fibonacci::fib_big(n as u64), BigUint::from(expected));
+    }
+
+    #[test]
+    fn wide_algorithms_agree(n in 0u32..=186) {
+        let expected = fibonacci::fib_big(n as u64);
+        prop_assert_eq!(fibonacci::fib_u128(n).map(BigUint::from), Some(expected.clone()));
+        prop_assert_eq!(fibonacci::fib_checked(n).is_some(), n <= MAX_U64_N);
+        // F(n) = F(n-1) + F(n-2) also holds for the pair function
+        let (a, b) = fibonacci::fib_big_pair(n as u64);
+        prop_assert_eq!(&a, &expected);
+        prop_assert_eq!(b, fibonacci::fib_big(n as u64 + 1));
+    }
+
+    #[test]
+    fn modular_agrees_with_exact(n in 0u64..2_000, m in 1u64..=u64::MAX) {
+        let expected = fibonacci::fib_big(n) % BigUint::from(m);
+        prop_assert_eq!(BigUint::from(fibonacci::fib_mod(n, m).unwrap()), expected);
+    }
+
+    #[test]
+    fn pisano_period_repeats(m in 1u64..2_000, n in 0u64..10_000) {
+        let period = fibonacci::pisano_period(m).unwrap();
+        prop_assert_eq!(fibonacci::fib_mod(n + period, m), fibonacci::fib_mod(n, m));
+    }
+
+    #[test]
+    fn membership_matches_the_sequence(x in any::<u64>()) {
+        let sequence = fibonacci::generate_sequence_iterative(MAX_U64_N);
+        prop_assert_eq!(fibonacci::is_fibonacci_number(x), sequence.contains(&x));
+    }
+
+    #[test]
+    fn membership_near_fibonacci_numbers(n in 0..=MAX_U64_N, delta in -3i64..=3) {
+        let sequence = fibonacci::generate_sequence_iterative(MAX_U64_N);
+        if let Some(x) = sequence[n as usize].checked_add_signed(delta) {
+            prop_assert_eq!(fibonacci::is_fibonacci_number(x), sequence.contains(&x));
+            prop_assert_eq!(fibonacci::fibonacci_index(x).is_some(), sequence.contains(&x));
+        }
+    }
+
+    #[test]
+    fn index_inverts_fib(n in 0..=MAX_U64_N) {
+        let value = fibonacci::fib_checked(n).unwrap();
+        let index = fibonacci::fibonacci_index(value).unwrap();
+        // F(1) = F(2) = 1, and the smallest index is reported
+        prop_assert_eq!(fibonacci::fib_checked(index), Some(value));
+        prop_assert!(index == n || (n == 2 && index == 1));
+    }
+
+    #[test]
+    fn cassini_identity(n in 1u64..3_000) {
+        // F(n-1)·F(n+1) - F(n)² = (-1)^n
+        let f = |k: u64| BigInt::from(fibonacci::fib_big(k));
+        let expected = if n % 2 == 0 { BigInt::from(1) } else { BigInt::from(-1) };
+        prop_assert_eq!(f(n - 1) * f(n + 1) - f(n) * f(n), expected);
+    }
+
+    #[test]
+    fn gcd_identity(m in 0..=MAX_U64_N, n in 0..=MAX_U64_N) {
+        // gcd(F(m), F(n)) = F(gcd(m, n))
+        let f = |k: u32| fibonacci::fib_checked(k).unwrap();
+        prop_assert_eq!(gcd(f(m), f(n)), f(gcd(m as u64, n as u64) as u32));
+    }
+
+    #[test]
+    fn validate_input_never_panics(input in any::<String>()) {
+        if let Ok(n) = validation::validate_input(&input) {
+            prop_assert!(n <= MAX_FIBONACCI_N);
+            prop_assert_eq!(input.trim().parse::<u32>(), Ok(n));
+        }
+        prop_assert_eq!(validation::is_valid_input(&input), validation::validate_input(&input).is_ok());
+    }
+
+    #[test]
+    fn validate_input_accepts_exactly_the_range(n in any::<u64>(), pad in "[ \t]{0,3}") {
+        let input = format!("{}{}{}", pad, n, pad);
+        let result = validation::validate_input(&input);
+        if n <= MAX_FIBONACCI_N as u64 {
+            prop_assert_eq!(result, Ok(n as u32));
+        } else {
+            prop_assert_eq!(result, Err(InputError::TooLarge { value: n.to_string(), max: MAX_FIBONACCI_N as u64 }));
+        }
+    }
+
+    #[test]
+    fn other_validators_never_panic(a in "\\PC*", b in "[0-9 ]{0,25}", max in any::<u32>()) {
+        let _ = validation::validate_input_with_max(&a, max);
+        let _ = validation::validate_value(&a);
+        let _ = validation::validate_modulus(&a);
+        let _ = validation::validate_big_value(&a);
+        if let Ok((from, to)) = validation::validate_range(&a, &b, max) {
+            prop_assert!(from <= to && to <= max);
+        }
+    }
+
+    #[test]
+    fn spiral_rectangles_stay_inside_and_apart(
+        n in 0..=MAX_U64_N,
+        x in -1_000.0f32..1_000.0,
+        y in -1_000.0f32..1_000.0,
+        width in 0.0f32..2_000.0,
+        height in 0.0f32..2_000.0,
+    ) {
+        let target = Rect::from_min_size(Pos2::new(x, y), Vec2::new(width, height));
+        let rectangles = spiral_rectangles(target, &fibonacci::generate_sequence_iterative(n));
+        // Allow for rounding in the f32 layout
+        let tolerance = 1e-3 * (1.0 + x.abs().max(y.abs()) + width.max(height));
+
+        for (i, a) in rectangles.iter().enumerate() {
+            prop_assert!(
+                target.expand(tolerance).contains_rect(a.rect),
+                "{:?} (F({})) is outside {:?}", a.rect, a.index, target
+            );
+            for b in &rectangles[i + 1..] {
+                let overlap = a.rect.intersect(b.rect);
+                prop_assert!(
+                    !overlap.is_positive() || overlap.width().min(overlap.height()) <= tolerance,
+                    "{:?} (F({})) overlaps {:?} (F({}))", a.rect, a.index, b.rect, b.index
+                );
+            }
+        }
+    }
+}
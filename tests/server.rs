@@ -0,0 +1,70 @@
+//! Runs the HTTP API on a free localhost port and talks to it over TCP.
+#![cfg(feature = "server")]
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::Arc;
+use std::thread;
+
+use fibonacci_sequence::server::Server;
+use serde_json::{Value, json};
+
+/// Send a request and return the status code, Content-Type and parsed body
+fn request(addr: SocketAddr, method: &str, path: &str) -> (u16, String, Value) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n", method, path).unwrap();
+    let mut raw = String::new();
+    stream.read_to_string(&mut raw).unwrap();
+
+    let (head, body) = raw.split_once("\r\n\r\n").unwrap();
+    let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+    let content_type = head
+        .lines()
+        .find_map(|line| line.strip_prefix("Content-Type: "))
+        .unwrap_or_default()
+        .to_string();
+    (status, content_type, serde_json::from_str(body).unwrap())
+}
+
+#[test]
+fn test_server_over_localhost() {
+    let server = Arc::new(Server::bind("127.0.0.1:0").unwrap());
+    let addr = server.local_addr().unwrap();
+    let worker = {
+        let server = Arc::clone(&server);
+        thread::spawn(move || server.run())
+    };
+
+    let (status, content_type, body) = request(addr, "GET", "/fib/100");
+    assert_eq!(status, 200);
+    assert_eq!(content_type, "application/json");
+    assert_eq!(body, json!({ "n": 100, "value": "354224848179261915075" }));
+
+    let (status, _, body) = request(addr, "GET", "/seq?from=0&to=4");
+    assert_eq!(status, 200);
+    assert_eq!(body["values"], json!(["0", "1", "1", "2", "3"]));
+
+    let (_, _, body) = request(addr, "GET", "/mod/100/1000000007");
+    assert_eq!(body["value"], (354224848179261915075u128 % 1_000_000_007).to_string());
+
+    let (_, _, body) = request(addr, "GET", "/is/354224848179261915075");
+    assert_eq!(body["index"], 100);
+
+    let (_, _, body) = request(addr, "GET", "/zeckendorf/1000");
+    assert_eq!(body["terms"], json!(["987", "13"]));
+
+    // Requests that would starve the server are refused up front
+    let (status, _, body) = request(addr, "GET", "/fib/1000000000000");
+    assert_eq!(status, 400);
+    assert!(body["error"].as_str().unwrap().contains("too large"));
+
+    let (status, _, _) = request(addr, "DELETE", "/fib/1");
+    assert_eq!(status, 405);
+
+    let (status, _, spec) = request(addr, "GET", "/openapi.json");
+    assert_eq!(status, 200);
+    assert_eq!(spec["info"]["title"], "Fibonacci API");
+
+    server.shutdown();
+    worker.join().unwrap();
+}
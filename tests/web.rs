@@ -0,0 +1,75 @@
+//! Drives the app logic without a window, natively and in a headless browser:
+//!
+//! ```bash
+//! cargo test --test web
+//! wasm-pack test --headless --firefox -- --no-default-features --features egui-app --test web
+//! ```
+#![cfg(feature = "egui-app")]
+
+use std::path::PathBuf;
+
+use eframe::egui;
+use fibonacci_sequence::app::FibonacciApp;
+use fibonacci_sequence::export;
+use fibonacci_sequence::fibonacci::{self, Algorithm};
+use fibonacci_sequence::history::HistoryEntry;
+use fibonacci_sequence::ui::SpiralVisualization;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[cfg(target_arch = "wasm32")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+/// Lay out one frame of the spiral and return how many shapes it painted
+fn paint_spiral(n: u32) -> usize {
+    let ctx = egui::Context::default();
+    let sequence = fibonacci::generate_sequence_iterative(n);
+    let mut spiral = SpiralVisualization::default();
+    let output = ctx.run(egui::RawInput::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| spiral.render(ui, &sequence, n));
+    });
+    output.shapes.len()
+}
+
+#[test]
+fn spiral_paints_without_a_window() {
+    assert!(paint_spiral(10) > paint_spiral(0));
+    assert!(paint_spiral(25) > paint_spiral(5));
+}
+
+#[test]
+fn app_loads_a_sequence() {
+    let mut app = FibonacciApp::new();
+    app.load_sequence(fibonacci::generate_sequence_iterative(20)).unwrap();
+    assert_eq!(app.get_current_n(), 20);
+    assert_eq!(app.get_sequence().last(), Some(&6765));
+    assert!(app.has_results());
+}
+
+#[test]
+fn app_calculates() {
+    // Recording the result reads the clock, which std cannot do in the browser
+    let mut app = FibonacciApp::new();
+    app.calculate("12");
+    assert_eq!(app.get_current_n(), 12);
+    assert_eq!(app.get_sequence(), fibonacci::generate_sequence_iterative(12));
+    assert!(HistoryEntry::new(12, Algorithm::Iterative, 144).time > 0);
+}
+
+#[test]
+fn export_round_trips_through_platform_storage() {
+    // A file on native targets, a localStorage entry in the browser
+    let path = if cfg!(target_arch = "wasm32") {
+        PathBuf::from("web_test.json")
+    } else {
+        std::env::temp_dir().join(format!("fibonacci_web_test_{}.json", std::process::id()))
+    };
+    let sequence = fibonacci::generate_sequence_iterative(15);
+    export::export_to_file(&path, &sequence).unwrap();
+    assert_eq!(export::import_from_file(&path), Ok(sequence));
+    assert!(export::import_from_file(&path.with_extension("csv")).is_err());
+
+    #[cfg(not(target_arch = "wasm32"))]
+    std::fs::remove_file(&path).unwrap();
+}